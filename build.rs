@@ -0,0 +1,42 @@
+use std::{env, fs, path::Path};
+
+fn main() {
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let puzzles_dir = Path::new(&manifest_dir).join("assets/puzzles");
+
+    println!("cargo:rerun-if-changed={}", puzzles_dir.display());
+
+    let mut files = fs::read_dir(&puzzles_dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| {
+            path.extension()
+                .map_or(false, |extension| extension == "ron")
+        })
+        .collect::<Vec<_>>();
+
+    // Puzzles are listed in file name order, so a numeric prefix sets their order.
+    files.sort();
+
+    let entries = files
+        .iter()
+        .map(|path| {
+            format!(
+                "    ({:?}, include_str!({:?})),\n",
+                path.file_name().unwrap().to_string_lossy(),
+                path.display().to_string()
+            )
+        })
+        .collect::<String>();
+    let contents = format!(
+        "pub const PUZZLES: [(&str, &str); {}] = [\n{}];\n",
+        files.len(),
+        entries
+    );
+
+    fs::write(
+        Path::new(&env::var("OUT_DIR").unwrap()).join("puzzles.rs"),
+        contents,
+    )
+    .unwrap();
+}
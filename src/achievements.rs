@@ -0,0 +1,139 @@
+use bevy::prelude::*;
+
+use crate::{cards::CardType, menu::MenuSelection};
+
+pub const ACHIEVEMENTS: [Achievement; 5] = [
+    Achievement {
+        id: "flawless",
+        name: "Flawless",
+        description: "Win without losing a heart",
+        condition: Condition::FlawlessWin,
+    },
+    Achievement {
+        id: "pitchfork-mob",
+        name: "Pitchfork Mob",
+        description: "Win with only Pitchforks on the board",
+        condition: Condition::WinWithOnly(CardType::Pitchfork),
+    },
+    Achievement {
+        id: "no-casualties",
+        name: "No Casualties",
+        description: "Win without losing a card",
+        condition: Condition::WinWithoutLosses,
+    },
+    Achievement {
+        id: "siege-engine",
+        name: "Siege Engine",
+        description: "Deal 10 tower damage in one turn",
+        condition: Condition::TowerDamageInTurn(10),
+    },
+    Achievement {
+        id: "conqueror",
+        name: "Conqueror",
+        description: "Beat the Large tower",
+        condition: Condition::WinOn(MenuSelection::Large),
+    },
+];
+
+pub struct Achievement {
+    pub id: &'static str,
+    pub name: &'static str,
+    pub description: &'static str,
+    pub condition: Condition,
+}
+
+impl Achievement {
+    pub fn find(id: &str) -> Option<&'static Achievement> {
+        ACHIEVEMENTS.iter().find(|achievement| achievement.id == id)
+    }
+}
+
+#[derive(Component)]
+pub struct AchievementToast {
+    pub timer: Timer,
+}
+
+impl Default for AchievementToast {
+    fn default() -> Self {
+        Self {
+            timer: Timer::from_seconds(3.0, TimerMode::Once),
+        }
+    }
+}
+
+#[derive(Default, Resource)]
+pub struct AchievementProgress {
+    pub board: Vec<CardType>,
+    pub cards_lost: u32,
+    pub hearts_lost: u32,
+    pub tower_damage_this_turn: u32,
+    pub won_on: Option<MenuSelection>,
+}
+
+pub enum Condition {
+    FlawlessWin,
+    TowerDamageInTurn(u32),
+    WinOn(MenuSelection),
+    WinWithOnly(CardType),
+    WinWithoutLosses,
+}
+
+impl Condition {
+    pub fn met(&self, progress: &AchievementProgress) -> bool {
+        let won = progress.won_on.is_some();
+
+        match self {
+            Self::FlawlessWin => won && progress.hearts_lost == 0,
+            Self::TowerDamageInTurn(damage) => progress.tower_damage_this_turn >= *damage,
+            Self::WinOn(selection) => progress.won_on == Some(*selection),
+            Self::WinWithOnly(card_type) => {
+                won && !progress.board.is_empty()
+                    && progress.board.iter().all(|placed| placed == card_type)
+            }
+            Self::WinWithoutLosses => won && progress.cards_lost == 0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn unlocked(progress: &AchievementProgress) -> Vec<&'static str> {
+        ACHIEVEMENTS
+            .iter()
+            .filter(|achievement| achievement.condition.met(progress))
+            .map(|achievement| achievement.id)
+            .collect()
+    }
+
+    #[test]
+    fn conditions_follow_match_progress() {
+        let mut progress = AchievementProgress {
+            board: vec![CardType::Pitchfork, CardType::Pitchfork],
+            tower_damage_this_turn: 4,
+            ..default()
+        };
+
+        assert!(unlocked(&progress).is_empty());
+
+        progress.hearts_lost = 2;
+        progress.tower_damage_this_turn = 10;
+        progress.won_on = Some(MenuSelection::Large);
+
+        assert_eq!(
+            unlocked(&progress),
+            vec![
+                "pitchfork-mob",
+                "no-casualties",
+                "siege-engine",
+                "conqueror"
+            ]
+        );
+
+        progress.board.push(CardType::Sword);
+        progress.cards_lost = 1;
+
+        assert_eq!(unlocked(&progress), vec!["siege-engine", "conqueror"]);
+    }
+}
@@ -6,20 +6,75 @@ use crate::cards::CardType;
 pub const BOARD_HEIGHT: f32 = 0.25;
 pub const BLOCK_SIZE: f32 = 1.0;
 
+pub struct AttackForecast {
+    pub card_counter_damage: i32,
+    pub counter_damage: i32,
+    pub lanes: Vec<LaneForecast>,
+    pub tower_damage: i32,
+}
+
+impl AttackForecast {
+    pub fn new<A: Board, B: Board>(
+        attacking: &A,
+        defending: &B,
+        stats: impl Fn(Entity) -> (i32, i32),
+    ) -> Self {
+        let mut forecast = Self {
+            card_counter_damage: 0,
+            counter_damage: 0,
+            lanes: Vec::new(),
+            tower_damage: 0,
+        };
+        let mut killed = Vec::new();
+
+        for (lane, placement) in attacking.state().board.iter().enumerate() {
+            if let Some(placement) = placement {
+                let (attack, _) = stats(placement.entity);
+                let damage = attack.max(0);
+                let target = attacking.across(defending.state(), placement.entity);
+                let kills = target.map_or(false, |target| stats(target.entity).1 <= damage);
+
+                if let Some(target) = target {
+                    if kills {
+                        killed.push(target.entity);
+                    }
+                } else {
+                    forecast.tower_damage += damage;
+                }
+
+                forecast.lanes.push(LaneForecast {
+                    damage,
+                    kills,
+                    lane,
+                    target: target.map(|target| target.card_type),
+                });
+            }
+        }
+
+        for placement in defending.all() {
+            if killed.contains(&placement.entity) {
+                continue;
+            }
+
+            let damage = stats(placement.entity).0.max(0);
+
+            if defending
+                .across(attacking.state(), placement.entity)
+                .is_some()
+            {
+                forecast.card_counter_damage += damage;
+            } else {
+                forecast.counter_damage += damage;
+            }
+        }
+
+        forecast
+    }
+}
+
 pub trait Board: Resource {
     fn across(&self, other: &BoardState, entity: Entity) -> Option<BoardPlacement> {
-        if let Some((i, _)) = self
-            .state()
-            .board
-            .iter()
-            .enumerate()
-            .filter(|(_, e)| e.is_some())
-            .find(|(_, e)| e.unwrap().entity == entity)
-        {
-            other.board[i]
-        } else {
-            None
-        }
+        self.state().across(other, entity)
     }
     fn adjacent(&self, entity: Entity) -> (Option<BoardPlacement>, Option<BoardPlacement>) {
         self.state().adjacent(entity)
@@ -46,7 +101,7 @@ pub trait Board: Resource {
     }
 }
 
-#[derive(Resource)]
+#[derive(Clone, Resource)]
 pub struct BoardState {
     board: [Option<BoardPlacement>; 4],
 }
@@ -56,6 +111,20 @@ impl BoardState {
         Self { board: [None; 4] }
     }
 
+    pub fn across(&self, other: &BoardState, entity: Entity) -> Option<BoardPlacement> {
+        if let Some((i, _)) = self
+            .board
+            .iter()
+            .enumerate()
+            .filter(|(_, e)| e.is_some())
+            .find(|(_, e)| e.unwrap().entity == entity)
+        {
+            other.board[i]
+        } else {
+            None
+        }
+    }
+
     pub fn adjacent(&self, entity: Entity) -> (Option<BoardPlacement>, Option<BoardPlacement>) {
         let index = self
             .board
@@ -82,6 +151,19 @@ impl BoardState {
         }
     }
 
+    // Ties go to the left so the choice doesn't depend on the rng.
+    pub fn empty_place_near(&self, entity: Entity) -> Option<u32> {
+        let index = self
+            .board
+            .iter()
+            .position(|e| e.map_or(false, |e| e.entity == entity))?;
+
+        (0..self.board.len())
+            .filter(|i| self.board[*i].is_none())
+            .min_by_key(|i| i.abs_diff(index))
+            .map(|i| i as u32)
+    }
+
     pub fn others(&self, entity: Entity) -> impl Iterator<Item = BoardPlacement> + '_ {
         self.board
             .iter()
@@ -133,6 +215,13 @@ pub struct BoardPlacement {
     pub card_type: CardType,
 }
 
+pub struct LaneForecast {
+    pub damage: i32,
+    pub kills: bool,
+    pub lane: usize,
+    pub target: Option<CardType>,
+}
+
 #[derive(Resource)]
 pub struct OpponentBoard {
     board_state: BoardState,
@@ -149,9 +238,8 @@ impl OpponentBoard {
         self.board_state.board.iter().any(|e| e.is_none())
     }
 
-    pub fn random_empty_place(&self) -> Option<u32> {
+    pub fn random_empty_place(&self, rng: &mut impl Rng) -> Option<u32> {
         if self.board_state.board.into_iter().any(|e| e.is_none()) {
-            let mut rng = rand::thread_rng();
             let mut index = rng.gen_range(0..4);
 
             while self.board_state.board[index].is_some() {
@@ -198,3 +286,64 @@ impl Board for PlayerBoard {
 
 #[derive(Component)]
 pub struct TurnDial;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn forecast_counts_kills_tower_damage_and_counter_damage() {
+        let mut player = PlayerBoard::new();
+        let mut opponent = OpponentBoard::new();
+        let stats = HashMap::from([
+            (Entity::from_raw(0), (2, 2)),
+            (Entity::from_raw(1), (1, 1)),
+            (Entity::from_raw(2), (3, 2)),
+            (Entity::from_raw(3), (1, 3)),
+            (Entity::from_raw(4), (2, 1)),
+        ]);
+
+        player.place(0, Entity::from_raw(0), CardType::Sword);
+        player.place(1, Entity::from_raw(1), CardType::Pitchfork);
+        player.place(2, Entity::from_raw(2), CardType::Sword);
+        opponent.place(0, Entity::from_raw(3), CardType::Tower);
+        opponent.place(1, Entity::from_raw(4), CardType::Sword);
+
+        let forecast = AttackForecast::new(&player, &opponent, |entity| stats[&entity]);
+
+        assert_eq!(forecast.lanes.len(), 3);
+        assert!(!forecast.lanes[0].kills);
+        assert!(forecast.lanes[1].kills);
+        assert!(forecast.lanes[2].target.is_none());
+        assert_eq!(forecast.tower_damage, 3);
+        assert_eq!(forecast.counter_damage, 0);
+        assert_eq!(forecast.card_counter_damage, 1);
+
+        player.remove(Entity::from_raw(0));
+
+        let forecast = AttackForecast::new(&player, &opponent, |entity| stats[&entity]);
+
+        assert_eq!(forecast.counter_damage, 1);
+        assert_eq!(forecast.card_counter_damage, 0);
+    }
+
+    #[test]
+    fn empty_place_near_prefers_the_closest_lane() {
+        let mut board = PlayerBoard::new();
+
+        board.place(1, Entity::from_raw(0), CardType::Phoenix);
+
+        assert_eq!(board.state().empty_place_near(Entity::from_raw(0)), Some(0));
+
+        board.place(0, Entity::from_raw(1), CardType::Sword);
+
+        assert_eq!(board.state().empty_place_near(Entity::from_raw(0)), Some(2));
+
+        board.place(2, Entity::from_raw(2), CardType::Sword);
+        board.place(3, Entity::from_raw(3), CardType::Sword);
+
+        assert_eq!(board.state().empty_place_near(Entity::from_raw(0)), None);
+        assert_eq!(board.state().empty_place_near(Entity::from_raw(4)), None);
+    }
+}
@@ -1,8 +1,9 @@
-use bevy::prelude::*;
+use bevy::{prelude::*, render::mesh::VertexAttributeValues};
+use serde::Deserialize;
 
 use crate::board::BoardState;
 
-const ABILITY_MAX: i32 = 4;
+pub const ABILITY_MAX: i32 = 4;
 const ATTRIBUTE_HEART_OFFSET: f32 = 1.4;
 const ATTRIBUTE_GEM_OFFSET_X: f32 = -0.8;
 const ATTRIBUTE_GEM_OFFSET_Z: f32 = -1.2;
@@ -12,6 +13,9 @@ const ATTRIBUTE_SCALE: Vec3 = Vec3::new(0.5, 0.5, 0.5);
 const ATTRIBUTE_SWORD_OFFSET: f32 = 1.0;
 const ATTRIBUTE_WIDTH: f32 = 0.4;
 const ATTRIBUTE_X_OFFSET: f32 = 0.6;
+// Where the card artwork sits, measured from the models in assets/models.
+const ARTWORK_HEIGHT: f32 = 0.09;
+const ARTWORK_Z: f32 = -0.3;
 pub const CARD_THICKNESS: f32 = 0.05;
 pub const CARD_HALF_THICKNESS: f32 = CARD_THICKNESS / 2.0;
 pub const CARD_HEIGHT: f32 = 3.0;
@@ -89,11 +93,34 @@ pub struct CardAssets {
     pub gem_empty_material: Handle<StandardMaterial>,
     pub gem_material: Handle<StandardMaterial>,
     pub gem_mesh: Handle<Mesh>,
+    pub phoenix_material: Handle<StandardMaterial>,
+    pub phoenix_mesh: Handle<Mesh>,
     pub pitchfork_mesh: Handle<Mesh>,
     pub sword_mesh: Handle<Mesh>,
     pub tower_mesh: Handle<Mesh>,
 }
 
+// The Phoenix has no model of its own, so it gets a ring laid flat where the artwork goes.
+pub fn phoenix_mesh() -> Mesh {
+    let mut mesh = Mesh::from(shape::Torus {
+        radius: 0.3,
+        ring_radius: 0.05,
+        subdivisions_segments: 32,
+        subdivisions_sides: 12,
+    });
+
+    if let Some(VertexAttributeValues::Float32x3(positions)) =
+        mesh.attribute_mut(Mesh::ATTRIBUTE_POSITION)
+    {
+        for position in positions.iter_mut() {
+            position[1] += ARTWORK_HEIGHT;
+            position[2] += ARTWORK_Z;
+        }
+    }
+
+    mesh
+}
+
 pub struct Attributes {
     pub attack: u32,
     pub cost: u32,
@@ -104,6 +131,7 @@ pub enum CardAbility {
     AttackUpAdjacent,
     HealthUpAdjacent,
     HealthUpAll,
+    Resurrect,
     StrengthInNumbers,
 }
 
@@ -139,6 +167,7 @@ impl CardAbility {
                     affects.push(entity.entity);
                 }
             }
+            Self::Resurrect => {}
             Self::StrengthInNumbers => {
                 for entity in board.others_of_type(entity, card_type) {
                     affects.push(entity.entity);
@@ -149,6 +178,16 @@ impl CardAbility {
         affects
     }
 
+    pub fn description(&self) -> &'static str {
+        match self {
+            Self::AttackUpAdjacent => "Adjacent cards get +1 attack.",
+            Self::HealthUpAdjacent => "Adjacent cards get +1 health.",
+            Self::HealthUpAll => "All other cards get +1 health.",
+            Self::Resurrect => "Returns the last fallen card to an empty lane, or to hand if full.",
+            Self::StrengthInNumbers => "Other cards of the same type get +1 attack.",
+        }
+    }
+
     pub fn effect(&self) -> CardAbilityEffect {
         match self {
             Self::AttackUpAdjacent | Self::StrengthInNumbers => CardAbilityEffect {
@@ -159,40 +198,146 @@ impl CardAbility {
                 attack: 0,
                 health: 1,
             },
+            Self::Resurrect => CardAbilityEffect {
+                attack: 0,
+                health: 0,
+            },
         }
     }
 }
 
-#[derive(Debug)]
+#[derive(Clone, Copy, Debug)]
 pub struct CardAbilityEffect {
     pub attack: i32,
     pub health: i32,
 }
 
-impl CardAbilityEffect {
-    pub fn apply(&self, attack: &mut Attack, health: &mut Health) {
-        attack.0 = (attack.0 + self.attack).min(ABILITY_MAX);
-        health.0 = (health.0 + self.health).min(ABILITY_MAX);
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Stat {
+    Attack,
+    Health,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ModifierSource {
+    Card(Entity),
+    Puzzle,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct StatModifier {
+    pub delta: i32,
+    pub expires: Option<u32>,
+    pub source: ModifierSource,
+    pub stat: Stat,
+}
+
+#[derive(Component, Default)]
+pub struct StatModifiers {
+    damage: i32,
+    modifiers: Vec<StatModifier>,
+}
+
+impl StatModifiers {
+    pub fn add(&mut self, source: Entity, effect: CardAbilityEffect) {
+        self.add_from(ModifierSource::Card(source), effect);
+    }
+
+    pub fn add_from(&mut self, source: ModifierSource, effect: CardAbilityEffect) {
+        self.add_until(source, effect, None);
+    }
+
+    // Modifiers with an expiry turn are dropped when the owning side reaches that turn.
+    pub fn add_until(
+        &mut self,
+        source: ModifierSource,
+        effect: CardAbilityEffect,
+        expires: Option<u32>,
+    ) {
+        for (stat, delta) in [(Stat::Attack, effect.attack), (Stat::Health, effect.health)] {
+            if delta != 0 {
+                self.modifiers.push(StatModifier {
+                    delta,
+                    expires,
+                    source,
+                    stat,
+                });
+            }
+        }
+    }
+
+    pub fn attack(&self, card_type: CardType) -> Attack {
+        Attack(self.total(card_type.attributes().attack, Stat::Attack))
+    }
+
+    pub fn contains(&self, source: Entity) -> bool {
+        self.modifiers
+            .iter()
+            .any(|modifier| modifier.source == ModifierSource::Card(source))
+    }
+
+    pub fn expire(&mut self, turn: u32) {
+        self.modifiers
+            .retain(|modifier| modifier.expires.map_or(true, |expires| expires > turn));
+    }
+
+    pub fn health(&self, card_type: CardType) -> Health {
+        Health(self.total(card_type.attributes().health, Stat::Health) - self.damage)
+    }
+
+    pub fn modifiers(&self, stat: Stat) -> impl Iterator<Item = &StatModifier> {
+        self.modifiers
+            .iter()
+            .filter(move |modifier| modifier.stat == stat)
     }
 
-    pub fn remove(&self, attack: &mut Attack, health: &mut Health) {
-        attack.0 = (attack.0 - self.attack).max(0);
-        health.0 = (health.0 - self.health).max(0);
+    pub fn remove(&mut self, source: Entity) {
+        self.modifiers
+            .retain(|modifier| modifier.source != ModifierSource::Card(source));
+    }
+
+    pub fn sync(&self, card_type: CardType, attack: &mut Attack, health: &mut Health) {
+        *attack = self.attack(card_type);
+        *health = self.health(card_type);
+    }
+
+    pub fn take_damage(&mut self, damage: i32) {
+        self.damage += damage;
+    }
+
+    fn total(&self, base: u32, stat: Stat) -> i32 {
+        let total = self
+            .modifiers(stat)
+            .fold(base as i32, |total, modifier| total + modifier.delta);
+
+        total.min(ABILITY_MAX)
     }
 }
 
-#[derive(Clone, Copy, Component, Debug, PartialEq, Eq)]
+#[derive(Clone, Copy, Component, Debug, Deserialize, PartialEq, Eq)]
 pub enum CardType {
     Heart,
+    Phoenix,
     Pitchfork,
     Sword,
     Tower,
 }
 
 impl CardType {
+    pub fn all() -> [Self; 5] {
+        [
+            Self::Heart,
+            Self::Phoenix,
+            Self::Pitchfork,
+            Self::Sword,
+            Self::Tower,
+        ]
+    }
+
     pub fn ability(&self) -> CardAbility {
         match self {
             Self::Heart => CardAbility::HealthUpAll,
+            Self::Phoenix => CardAbility::Resurrect,
             Self::Pitchfork => CardAbility::StrengthInNumbers,
             Self::Sword => CardAbility::AttackUpAdjacent,
             Self::Tower => CardAbility::HealthUpAdjacent,
@@ -210,6 +355,11 @@ impl CardType {
                 cost: 2,
                 health: 1,
             },
+            Self::Phoenix => Attributes {
+                attack: 1,
+                cost: 3,
+                health: 1,
+            },
             Self::Pitchfork => Attributes {
                 attack: 1,
                 cost: 1,
@@ -228,6 +378,15 @@ impl CardType {
         }
     }
 
+    pub fn deck_weight(&self) -> u32 {
+        match self {
+            Self::Heart => 3,
+            Self::Phoenix => 1,
+            Self::Pitchfork => 5,
+            Self::Sword | Self::Tower => 2,
+        }
+    }
+
     pub fn effect(&self) -> CardAbilityEffect {
         self.ability().effect()
     }
@@ -235,6 +394,7 @@ impl CardType {
     pub fn material(&self, assets: &CardAssets) -> Handle<StandardMaterial> {
         match self {
             Self::Heart => assets.heart_material.clone(),
+            Self::Phoenix => assets.phoenix_material.clone(),
             _ => assets.black_material.clone(),
         }
     }
@@ -242,16 +402,81 @@ impl CardType {
     pub fn mesh(&self, assets: &CardAssets) -> Handle<Mesh> {
         match self {
             Self::Heart => assets.heart_mesh.clone(),
+            Self::Phoenix => assets.phoenix_mesh.clone(),
             Self::Pitchfork => assets.pitchfork_mesh.clone(),
             Self::Sword => assets.sword_mesh.clone(),
             Self::Tower => assets.tower_mesh.clone(),
         }
     }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Heart => "Heart",
+            Self::Phoenix => "Phoenix",
+            Self::Pitchfork => "Pitchfork",
+            Self::Sword => "Sword",
+            Self::Tower => "Tower",
+        }
+    }
+
+    pub fn unlock(&self) -> CardUnlock {
+        match self {
+            Self::Heart | Self::Pitchfork | Self::Sword | Self::Tower => CardUnlock::Starter,
+            Self::Phoenix => CardUnlock::Achievements(1),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CardUnlock {
+    Achievements(u32),
+    Starter,
+}
+
+impl CardUnlock {
+    pub fn description(&self) -> String {
+        match self {
+            Self::Achievements(1) => "unlock an achievement".to_string(),
+            Self::Achievements(count) => format!("unlock {count} achievements"),
+            Self::Starter => "starter card".to_string(),
+        }
+    }
 }
 
 #[derive(Component)]
 pub struct PendingAbility;
 
+#[derive(Clone, Copy, Component, Debug, PartialEq, Eq)]
+pub enum Preview {
+    Buffed,
+    BuffedBy,
+    Facing,
+}
+
+impl Preview {
+    pub fn material(&self, materials: &PreviewMaterials) -> Handle<StandardMaterial> {
+        match self {
+            Self::Buffed => materials.buffed.clone(),
+            Self::BuffedBy => materials.buffed_by.clone(),
+            Self::Facing => materials.facing.clone(),
+        }
+    }
+
+    pub fn scale(&self) -> f32 {
+        match self {
+            Self::Buffed | Self::Facing => 1.1,
+            Self::BuffedBy => 1.2,
+        }
+    }
+}
+
+#[derive(Resource)]
+pub struct PreviewMaterials {
+    pub buffed: Handle<StandardMaterial>,
+    pub buffed_by: Handle<StandardMaterial>,
+    pub facing: Handle<StandardMaterial>,
+}
+
 pub trait Sigil {
     fn at_index(index: u32) -> Self;
     fn direction() -> f32 {
@@ -358,3 +583,51 @@ impl Sigil for HealthSigil {
         ATTRIBUTE_HEART_OFFSET
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn removing_a_capped_aura_restores_stats() {
+        let mut modifiers = StatModifiers::default();
+        let sword = Entity::from_raw(0);
+        let effect = CardAbility::AttackUpAdjacent.effect();
+
+        for source in 0..3 {
+            modifiers.add(Entity::from_raw(source), effect);
+        }
+
+        assert_eq!(modifiers.attack(CardType::Sword).0, ABILITY_MAX);
+
+        modifiers.remove(sword);
+
+        assert_eq!(modifiers.attack(CardType::Sword).0, ABILITY_MAX);
+        assert_eq!(modifiers.health(CardType::Sword).0, 2);
+    }
+
+    #[test]
+    fn temporary_modifiers_expire() {
+        let mut modifiers = StatModifiers::default();
+
+        modifiers.add_until(
+            ModifierSource::Card(Entity::from_raw(0)),
+            CardAbilityEffect {
+                attack: 0,
+                health: 2,
+            },
+            Some(3),
+        );
+        modifiers.take_damage(1);
+
+        assert_eq!(modifiers.health(CardType::Sword).0, 3);
+
+        modifiers.expire(2);
+
+        assert_eq!(modifiers.health(CardType::Sword).0, 3);
+
+        modifiers.expire(3);
+
+        assert_eq!(modifiers.health(CardType::Sword).0, 1);
+    }
+}
@@ -1,66 +1,154 @@
 use bevy::prelude::*;
-use rand::Rng;
+use rand::{rngs::StdRng, Rng, SeedableRng};
 
-use crate::{cards::CardType, players::AttackedEvent};
+use crate::{
+    cards::CardType,
+    players::{AttackedEvent, DamageSource},
+};
+
+pub const OPENING_HAND_SIZE: u32 = 3;
 
 #[derive(Component)]
 pub struct Deck(pub u32);
 
+#[derive(Component)]
+pub struct DiscardPile(pub u32);
+
 #[derive(Component)]
 pub struct Draw;
 
+#[derive(Clone, Copy)]
+pub struct FallenCard {
+    pub card_type: CardType,
+    pub turn: u32,
+}
+
+#[derive(Default)]
+pub struct Graveyard {
+    cards: Vec<FallenCard>,
+}
+
+impl Graveyard {
+    pub fn bury(&mut self, card_type: CardType, turn: u32) {
+        self.cards.push(FallenCard { card_type, turn });
+    }
+
+    pub fn cards(&self) -> &[FallenCard] {
+        &self.cards
+    }
+
+    pub fn exhume(&mut self) -> Option<FallenCard> {
+        let (index, _) = self
+            .cards
+            .iter()
+            .enumerate()
+            .max_by_key(|(_, card)| card.turn)?;
+
+        Some(self.cards.remove(index))
+    }
+
+    fn empty(&mut self) -> Vec<CardType> {
+        self.cards.drain(..).map(|card| card.card_type).collect()
+    }
+
+    pub fn size(&self) -> u32 {
+        self.cards.len() as u32
+    }
+}
+
 struct DeckState {
     cards: Vec<CardType>,
+    rng: StdRng,
+    stacked: bool,
 }
 
 impl DeckState {
-    fn new(size: u32) -> Self {
-        let mut rng = rand::thread_rng();
+    fn new(size: u32, seed: u64, pool: &[CardType]) -> Self {
+        let mut rng = StdRng::seed_from_u64(seed);
         let mut cards = Vec::new();
+        let total = pool.iter().map(CardType::deck_weight).sum::<u32>();
+
+        if total == 0 {
+            return Self {
+                cards,
+                rng,
+                stacked: false,
+            };
+        }
 
         for _ in 0..size {
-            let n = rng.gen_range(0..12);
+            let mut n = rng.gen_range(0..total);
 
-            match n {
-                0 | 1 | 2 => {
-                    cards.push(CardType::Heart);
-                }
-                3 | 4 => {
-                    cards.push(CardType::Sword);
-                }
-                5 | 6 => {
-                    cards.push(CardType::Tower);
-                }
-                _ => {
-                    cards.push(CardType::Pitchfork);
+            for card_type in pool {
+                if n < card_type.deck_weight() {
+                    cards.push(*card_type);
+                    break;
                 }
+
+                n -= card_type.deck_weight();
             }
         }
 
-        Self { cards }
+        Self {
+            cards,
+            rng,
+            stacked: false,
+        }
     }
+
     fn draw(&mut self) -> Option<CardType> {
         if self.cards.is_empty() {
             return None;
         }
 
-        let mut rng = rand::thread_rng();
-        let index = rng.gen_range(0..self.cards.len());
+        // A stacked deck is drawn in order, from the front.
+        let index = if self.stacked {
+            0
+        } else {
+            self.rng.gen_range(0..self.cards.len())
+        };
 
         Some(self.cards.remove(index))
     }
 
+    fn reshuffle(&mut self, cards: Vec<CardType>) {
+        self.cards.extend(cards);
+    }
+
     fn size(&self) -> u32 {
         self.cards.len() as u32
     }
+
+    fn stacked(cards: Vec<CardType>) -> Self {
+        Self {
+            cards,
+            rng: StdRng::seed_from_u64(0),
+            stacked: true,
+        }
+    }
+}
+
+#[cfg(test)]
+impl From<Vec<CardType>> for DeckState {
+    fn from(cards: Vec<CardType>) -> Self {
+        Self {
+            cards,
+            rng: StdRng::seed_from_u64(0),
+            stacked: false,
+        }
+    }
 }
 
 #[derive(Resource)]
 pub struct OpponentState {
     pub available_power: i32,
     deck_state: DeckState,
+    fatigue: u32,
+    graveyard: Graveyard,
     hand: Vec<CardType>,
     health: i32,
+    max_health: i32,
+    pub max_hand_size: u32,
     pub max_power: u32,
     pub power: u32,
     pub turn: u32,
@@ -68,27 +156,89 @@ pub struct OpponentState {
 
 impl OpponentState {
     pub fn can_play_card(&self) -> bool {
-        self.deck_state
-            .cards
+        self.hand
             .iter()
             .any(|card| self.available_power >= card.attributes().cost as i32)
     }
 
+    pub fn hand_size(&self) -> u32 {
+        self.hand.len() as u32
+    }
+
+    #[cfg(test)]
+    pub fn with_cards(mut self, cards: Vec<CardType>) -> Self {
+        self.deck_state = cards.into();
+        self
+    }
+
     pub fn play_card(&mut self) -> Option<CardType> {
         let card_index = self
-            .deck_state
-            .cards
+            .hand
             .iter()
             .position(|card| self.available_power >= card.attributes().cost as i32)?;
 
-        Some(self.deck_state.cards.remove(card_index))
+        Some(self.hand.remove(card_index))
+    }
+
+    pub fn play_card_of(&mut self, card_type: CardType) -> Option<CardType> {
+        let card_index = self.hand.iter().position(|card| {
+            *card == card_type && self.available_power >= card.attributes().cost as i32
+        })?;
+
+        Some(self.hand.remove(card_index))
+    }
+
+    pub fn rng(&mut self) -> &mut StdRng {
+        &mut self.deck_state.rng
     }
 }
 
 impl OpponentState {
+    pub fn deal_opening_hand(&mut self) {
+        self.fill_hand(OPENING_HAND_SIZE);
+
+        let hearts = self
+            .hand
+            .iter()
+            .filter(|card| **card == CardType::Heart)
+            .count();
+
+        // A hand that is mostly Hearts has little to buff, send them back and try again.
+        if hearts * 2 > self.hand.len() {
+            let mulligan = vec![CardType::Heart; hearts];
+
+            self.hand.retain(|card| *card != CardType::Heart);
+            self.fill_hand(OPENING_HAND_SIZE);
+            self.deck_state.reshuffle(mulligan);
+        }
+    }
+
     pub fn draw_cards(&mut self) {
-        while self.hand.len() < 6 && !self.deck_state.cards.is_empty() {
-            self.hand.push(self.deck_state.draw().unwrap());
+        let draw_count = self.draw_count();
+
+        self.turn += 1;
+        self.fill_hand(self.hand_size() + draw_count);
+    }
+
+    fn fill_hand(&mut self, size: u32) {
+        let size = size.min(self.max_hand_size);
+
+        while self.hand_size() < size {
+            if let Some(card) = self.draw_card() {
+                self.hand.push(card);
+            } else {
+                break;
+            }
+        }
+    }
+
+    pub fn resurrect_to_hand(&mut self) {
+        if self.hand_size() >= self.max_hand_size {
+            return;
+        }
+
+        if let Some(card) = self.resurrect() {
+            self.hand.push(card);
         }
     }
 }
@@ -97,9 +247,13 @@ impl Default for OpponentState {
     fn default() -> Self {
         Self {
             available_power: 0,
-            deck_state: DeckState::new(12),
+            deck_state: DeckState::new(12, rand::random(), &CardType::all()),
+            fatigue: 0,
+            graveyard: Graveyard::default(),
             hand: Vec::new(),
             health: 12,
+            max_health: 12,
+            max_hand_size: 5,
             max_power: 5,
             power: 0,
             turn: 0,
@@ -108,14 +262,34 @@ impl Default for OpponentState {
 }
 
 impl PlayableState for OpponentState {
-    fn attacked_event(damage: u32) -> AttackedEvent {
-        AttackedEvent::Opponent(damage)
+    fn attacked_event(damage: u32, source: DamageSource) -> AttackedEvent {
+        AttackedEvent::Opponent(damage, source)
     }
 
     fn deck_size(&self) -> u32 {
         self.deck_state.size()
     }
 
+    fn discard_pile_translation() -> Vec3 {
+        Vec3::new(8.0, 0.0, -4.5)
+    }
+
+    fn draw_card(&mut self) -> Option<CardType> {
+        self.deck_state.draw()
+    }
+
+    fn draw_count(&self) -> u32 {
+        // The opening hand is dealt during the mulligan.
+        let count = if self.turn == 0 { 0 } else { 2 };
+
+        count.min(self.deck_size())
+    }
+
+    fn fatigue(&mut self) -> u32 {
+        self.fatigue += 1;
+        self.fatigue
+    }
+
     fn get_available_power(&self) -> i32 {
         self.available_power
     }
@@ -124,6 +298,10 @@ impl PlayableState for OpponentState {
         self.health
     }
 
+    fn get_max_health(&self) -> i32 {
+        self.max_health
+    }
+
     fn get_max_power(&self) -> u32 {
         self.max_power
     }
@@ -132,6 +310,23 @@ impl PlayableState for OpponentState {
         self.power
     }
 
+    fn get_turn(&self) -> u32 {
+        self.turn
+    }
+
+    fn graveyard(&self) -> &Graveyard {
+        &self.graveyard
+    }
+
+    fn graveyard_mut(&mut self) -> &mut Graveyard {
+        &mut self.graveyard
+    }
+
+    fn reshuffle_graveyard(&mut self) {
+        let cards = self.graveyard.empty();
+        self.deck_state.reshuffle(cards);
+    }
+
     fn set_available_power(&mut self, power: i32) {
         self.available_power = power;
     }
@@ -144,45 +339,69 @@ impl PlayableState for OpponentState {
         self.health -= damage;
     }
 
-    fn with_deck_size(mut self, size: u32) -> Self {
-        self.deck_state = DeckState::new(size);
+    fn with_deck_size(mut self, size: u32, seed: u64, pool: &[CardType]) -> Self {
+        self.deck_state = DeckState::new(size, seed, pool);
         self
     }
 
     fn with_health(mut self, health: i32) -> Self {
         self.health = health;
+        self.max_health = health;
+        self
+    }
+
+    fn with_stacked_deck(mut self, cards: Vec<CardType>) -> Self {
+        self.deck_state = DeckState::stacked(cards);
         self
     }
 }
 
 pub trait PlayableState: Resource {
-    fn attacked_event(damage: u32) -> AttackedEvent;
+    fn attacked_event(damage: u32, source: DamageSource) -> AttackedEvent;
+    fn bury(&mut self, card_type: CardType) {
+        let turn = self.get_turn();
+        self.graveyard_mut().bury(card_type, turn);
+    }
     fn deck_size(&self) -> u32;
+    fn discard_pile_translation() -> Vec3;
     fn draw_card(&mut self) -> Option<CardType> {
         None
     }
     fn draw_count(&self) -> u32 {
         0
     }
+    fn fatigue(&mut self) -> u32;
     fn get_available_power(&self) -> i32;
     fn get_health(&self) -> i32;
+    fn get_max_health(&self) -> i32;
     fn get_max_power(&self) -> u32;
     fn get_power(&self) -> u32;
+    fn get_turn(&self) -> u32;
+    fn graveyard(&self) -> &Graveyard;
+    fn graveyard_mut(&mut self) -> &mut Graveyard;
+    fn reshuffle_graveyard(&mut self);
+    fn resurrect(&mut self) -> Option<CardType> {
+        self.graveyard_mut().exhume().map(|card| card.card_type)
+    }
     fn set_available_power(&mut self, power: i32);
     fn set_power(&mut self, power: i32);
     fn show_power() -> bool {
         false
     }
     fn take_damage(&mut self, damage: i32);
-    fn with_deck_size(self, size: u32) -> Self;
+    fn with_deck_size(self, size: u32, seed: u64, pool: &[CardType]) -> Self;
     fn with_health(self, health: i32) -> Self;
+    fn with_stacked_deck(self, cards: Vec<CardType>) -> Self;
 }
 
 #[derive(Resource)]
 pub struct PlayerState {
     pub available_power: i32,
     deck_state: DeckState,
+    fatigue: u32,
+    graveyard: Graveyard,
     pub health: i32,
+    max_health: i32,
     pub max_hand_size: u32,
     pub max_power: u32,
     pub power: u32,
@@ -191,24 +410,34 @@ pub struct PlayerState {
 }
 
 impl PlayableState for PlayerState {
-    fn attacked_event(damage: u32) -> AttackedEvent {
-        AttackedEvent::Player(damage)
+    fn attacked_event(damage: u32, source: DamageSource) -> AttackedEvent {
+        AttackedEvent::Player(damage, source)
     }
 
     fn deck_size(&self) -> u32 {
         self.deck_state.size()
     }
 
+    fn discard_pile_translation() -> Vec3 {
+        Vec3::new(8.0, 0.0, 1.5)
+    }
+
     fn draw_card(&mut self) -> Option<CardType> {
         self.deck_state.draw()
     }
 
     fn draw_count(&self) -> u32 {
-        let count = if self.turn == 0 { 3 } else { 2 };
+        // The opening hand is dealt during the mulligan.
+        let count = if self.turn == 0 { 0 } else { 2 };
 
         count.min(self.deck_size())
     }
 
+    fn fatigue(&mut self) -> u32 {
+        self.fatigue += 1;
+        self.fatigue
+    }
+
     fn get_available_power(&self) -> i32 {
         self.available_power
     }
@@ -217,6 +446,10 @@ impl PlayableState for PlayerState {
         self.health
     }
 
+    fn get_max_health(&self) -> i32 {
+        self.max_health
+    }
+
     fn get_max_power(&self) -> u32 {
         self.max_power
     }
@@ -225,6 +458,23 @@ impl PlayableState for PlayerState {
         self.power
     }
 
+    fn get_turn(&self) -> u32 {
+        self.turn
+    }
+
+    fn graveyard(&self) -> &Graveyard {
+        &self.graveyard
+    }
+
+    fn graveyard_mut(&mut self) -> &mut Graveyard {
+        &mut self.graveyard
+    }
+
+    fn reshuffle_graveyard(&mut self) {
+        let cards = self.graveyard.empty();
+        self.deck_state.reshuffle(cards);
+    }
+
     fn set_available_power(&mut self, power: i32) {
         self.available_power = power;
     }
@@ -241,23 +491,44 @@ impl PlayableState for PlayerState {
         self.health -= damage;
     }
 
-    fn with_deck_size(mut self, size: u32) -> Self {
-        self.deck_state = DeckState::new(size);
+    fn with_deck_size(mut self, size: u32, seed: u64, pool: &[CardType]) -> Self {
+        self.deck_state = DeckState::new(size, seed, pool);
         self
     }
 
     fn with_health(mut self, health: i32) -> Self {
         self.health = health;
+        self.max_health = health;
+        self
+    }
+
+    fn with_stacked_deck(mut self, cards: Vec<CardType>) -> Self {
+        self.deck_state = DeckState::stacked(cards);
         self
     }
 }
 
+impl PlayerState {
+    #[cfg(test)]
+    pub fn with_cards(mut self, cards: Vec<CardType>) -> Self {
+        self.deck_state = cards.into();
+        self
+    }
+
+    pub fn return_to_deck(&mut self, card_type: CardType) {
+        self.deck_state.reshuffle(vec![card_type]);
+    }
+}
+
 impl Default for PlayerState {
     fn default() -> Self {
         Self {
             available_power: 0,
-            deck_state: DeckState::new(12),
+            deck_state: DeckState::new(12, rand::random(), &CardType::all()),
+            fatigue: 0,
+            graveyard: Graveyard::default(),
             health: 10,
+            max_health: 10,
             max_hand_size: 5,
             max_power: 5,
             power: 0,
@@ -266,3 +537,42 @@ impl Default for PlayerState {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn the_same_seed_deals_the_same_cards() {
+        let mut first = PlayerState::default().with_deck_size(24, 7, &CardType::all());
+        let mut second = PlayerState::default().with_deck_size(24, 7, &CardType::all());
+
+        for _ in 0..24 {
+            assert_eq!(first.draw_card(), second.draw_card());
+        }
+    }
+
+    #[test]
+    fn decks_only_contain_cards_from_the_pool() {
+        let pool = [CardType::Pitchfork, CardType::Sword];
+        let mut player = PlayerState::default().with_deck_size(40, 3, &pool);
+
+        assert_eq!(player.deck_size(), 40);
+
+        while let Some(card_type) = player.draw_card() {
+            assert!(pool.contains(&card_type));
+        }
+    }
+
+    #[test]
+    fn stacked_decks_are_drawn_in_order() {
+        let cards = vec![CardType::Sword, CardType::Heart, CardType::Tower];
+        let mut player = PlayerState::default().with_stacked_deck(cards.clone());
+
+        for card_type in cards {
+            assert_eq!(player.draw_card(), Some(card_type));
+        }
+
+        assert_eq!(player.draw_card(), None);
+    }
+}
@@ -8,9 +8,25 @@ pub struct CardPlayedEvent {
 #[derive(Component)]
 pub struct Hand(pub u32);
 
+#[derive(Component)]
+pub struct Mulligan;
+
+#[derive(Default, Resource)]
+pub struct MulliganState {
+    pub confirmed: bool,
+}
+
+#[derive(Component)]
+pub struct OpponentHand(pub u32);
+
 #[derive(Component)]
 pub struct Picked;
 
+pub struct PlayedCard {
+    pub entity: Entity,
+    pub hand: u32,
+}
+
 #[derive(Component)]
 pub struct Power {
     pub index: u32,
@@ -25,3 +41,8 @@ impl Power {
         }
     }
 }
+
+#[derive(Default, Resource)]
+pub struct UndoStack {
+    pub placements: Vec<PlayedCard>,
+}
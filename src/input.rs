@@ -0,0 +1,54 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Component)]
+pub struct FocusCursor;
+
+#[derive(Default, Resource)]
+pub struct Focus(pub Option<Entity>);
+
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq, Serialize)]
+pub enum InputAction {
+    Cancel,
+    Confirm,
+    Hand(u32),
+    Left,
+    NextHand,
+    PreviousHand,
+    Right,
+    Select,
+    Settings,
+    Undo,
+}
+
+impl InputAction {
+    pub fn all() -> Vec<Self> {
+        let mut actions = vec![Self::Confirm, Self::Select, Self::Cancel, Self::Undo];
+
+        actions.extend((0..9).map(Self::Hand));
+        actions.extend([
+            Self::PreviousHand,
+            Self::NextHand,
+            Self::Left,
+            Self::Right,
+            Self::Settings,
+        ]);
+
+        actions
+    }
+
+    pub fn label(&self) -> String {
+        match self {
+            Self::Cancel => "Cancel".to_string(),
+            Self::Confirm => "Confirm".to_string(),
+            Self::Hand(index) => format!("Hand slot {}", index + 1),
+            Self::Left => "Left".to_string(),
+            Self::NextHand => "Next card".to_string(),
+            Self::PreviousHand => "Previous card".to_string(),
+            Self::Right => "Right".to_string(),
+            Self::Select => "Select".to_string(),
+            Self::Settings => "Settings".to_string(),
+            Self::Undo => "Undo".to_string(),
+        }
+    }
+}
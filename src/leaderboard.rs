@@ -0,0 +1,217 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::cmp::Reverse;
+
+use crate::{
+    menu::{GameConfig, MenuSelection},
+    storage,
+};
+
+const SECONDS_PER_DAY: u64 = 60 * 60 * 24;
+
+#[derive(Component)]
+pub struct DailyButton;
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
+pub struct DailyResult {
+    pub day: u64,
+    pub health: i32,
+    #[serde(default)]
+    pub id: String,
+    pub player: String,
+    pub turns: u32,
+    pub won: bool,
+}
+
+impl DailyResult {
+    fn rank(&self) -> (bool, Reverse<u32>, i32) {
+        (self.won, Reverse(self.turns), self.health)
+    }
+}
+
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Resource, Serialize)]
+#[serde(default)]
+pub struct Leaderboard {
+    pub results: Vec<DailyResult>,
+}
+
+impl Leaderboard {
+    pub fn export(&self) -> bool {
+        storage::export("leaderboard-export", &self.results)
+    }
+
+    pub fn import(&mut self, local_id: &str) -> Option<usize> {
+        let results = storage::import::<Vec<DailyResult>>("leaderboard-import")?;
+
+        Some(self.merge(results, local_id))
+    }
+
+    pub fn load() -> Self {
+        storage::load("leaderboard")
+    }
+
+    // Results without an id, or claiming to be from this install, can't be told
+    // apart from local ones and are rejected.
+    pub fn merge(&mut self, results: Vec<DailyResult>, local_id: &str) -> usize {
+        results
+            .into_iter()
+            .filter(|result| !result.id.is_empty() && result.id != local_id)
+            .filter(|result| self.submit(result.clone()))
+            .count()
+    }
+
+    pub fn ranking(&self, day: u64) -> Vec<&DailyResult> {
+        let mut ranking = self
+            .results
+            .iter()
+            .filter(|result| result.day == day)
+            .collect::<Vec<_>>();
+
+        ranking.sort_by_key(|result| Reverse(result.rank()));
+        ranking
+    }
+
+    pub fn record(&mut self, id: &str, player: &str, day: u64, won: bool, turns: u32, health: i32) {
+        // Only the first attempt at each daily challenge counts, rematches don't.
+        if self
+            .results
+            .iter()
+            .any(|result| result.day == day && result.id == id)
+        {
+            return;
+        }
+
+        self.submit(DailyResult {
+            day,
+            health: health.max(0),
+            id: id.to_string(),
+            player: player.to_string(),
+            turns,
+            won,
+        });
+    }
+
+    pub fn save(&self) {
+        storage::save("leaderboard", self);
+    }
+
+    fn submit(&mut self, result: DailyResult) -> bool {
+        match self
+            .results
+            .iter_mut()
+            .find(|existing| existing.day == result.day && existing.id == result.id)
+        {
+            Some(existing) if result.rank() > existing.rank() => *existing = result,
+            Some(_) => return false,
+            None => self.results.push(result),
+        }
+
+        true
+    }
+
+    pub fn summary(&self, day: u64) -> String {
+        let mut lines = vec![format!("Daily challenge #{day}")];
+
+        for (i, result) in self.ranking(day).iter().enumerate() {
+            lines.push(format!(
+                "{}. {} - {} in {} turns, {} health left",
+                i + 1,
+                result.player,
+                if result.won { "won" } else { "lost" },
+                result.turns,
+                result.health
+            ));
+        }
+
+        lines.join("\n")
+    }
+}
+
+#[derive(Clone, Copy, Component)]
+pub enum LeaderboardButton {
+    Export,
+    Import,
+}
+
+#[derive(Component)]
+pub struct LeaderboardText;
+
+pub fn daily_config(day: u64) -> GameConfig {
+    MenuSelection::Medium
+        .game_config()
+        .with_daily(day)
+        .with_seed(day.wrapping_mul(0x9e37_79b9_7f4a_7c15))
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub fn today() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs() / SECONDS_PER_DAY)
+}
+
+#[cfg(target_arch = "wasm32")]
+pub fn today() -> u64 {
+    (js_sys::Date::now() / 1000.0) as u64 / SECONDS_PER_DAY
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(id: &str, player: &str, won: bool, turns: u32, health: i32) -> DailyResult {
+        DailyResult {
+            day: 7,
+            health,
+            id: id.to_string(),
+            player: player.to_string(),
+            turns,
+            won,
+        }
+    }
+
+    #[test]
+    fn every_player_gets_the_same_daily_game() {
+        let first = daily_config(19_000);
+        let second = daily_config(19_000);
+
+        assert_eq!(first.seed, second.seed);
+        assert_eq!(first.daily, Some(19_000));
+        assert_ne!(first.seed, daily_config(19_001).seed);
+    }
+
+    #[test]
+    fn imports_keep_each_players_best_finish() {
+        let mut leaderboard = Leaderboard::default();
+
+        leaderboard.record("local", "Bo", 7, true, 12, 4);
+        leaderboard.record("local", "Bo", 7, true, 5, 9);
+
+        let added = leaderboard.merge(
+            vec![
+                result("ana", "Ana", true, 10, 1),
+                result("ana", "Ana", true, 14, 8),
+                result("local", "Player", true, 5, 4),
+                result("", "Player", true, 5, 4),
+                result("sam", "Player", false, 20, 0),
+            ],
+            "local",
+        );
+
+        assert_eq!(added, 2);
+        assert_eq!(
+            leaderboard.summary(7),
+            "Daily challenge #7\n\
+             1. Ana - won in 10 turns, 1 health left\n\
+             2. Bo - won in 12 turns, 4 health left\n\
+             3. Player - lost in 20 turns, 0 health left"
+        );
+
+        let contents = serde_json::to_string(&leaderboard.results).unwrap();
+
+        assert_eq!(
+            serde_json::from_str::<Vec<DailyResult>>(&contents).unwrap(),
+            leaderboard.results
+        );
+    }
+}
@@ -0,0 +1,100 @@
+use bevy::{
+    asset::{Asset, LoadState},
+    prelude::*,
+};
+
+#[derive(Default, Resource)]
+pub struct LoadingAssets {
+    handles: Vec<HandleUntyped>,
+}
+
+impl LoadingAssets {
+    pub fn load<T: Asset>(&mut self, asset_server: &AssetServer, path: &str) -> Handle<T> {
+        let handle = asset_server.load(path);
+
+        self.handles.push(handle.clone_untyped());
+
+        handle
+    }
+
+    pub fn progress(&self, asset_server: &AssetServer) -> LoadProgress {
+        let mut progress = LoadProgress {
+            failed: Vec::new(),
+            loaded: 0,
+            total: self.handles.len(),
+        };
+
+        for handle in self.handles.iter() {
+            match asset_server.get_load_state(handle) {
+                LoadState::Loaded => progress.loaded += 1,
+                LoadState::Failed => progress.failed.push(
+                    asset_server
+                        .get_handle_path(handle)
+                        .map(|path| path.path().display().to_string())
+                        .unwrap_or_else(|| format!("{:?}", handle.id())),
+                ),
+                _ => {}
+            }
+        }
+
+        progress
+    }
+}
+
+#[derive(Component)]
+pub struct LoadingBar;
+
+#[derive(Component)]
+pub struct LoadingScreen;
+
+#[derive(Component)]
+pub struct LoadingText;
+
+pub struct LoadProgress {
+    pub failed: Vec<String>,
+    pub loaded: usize,
+    pub total: usize,
+}
+
+impl LoadProgress {
+    pub fn done(&self) -> bool {
+        self.loaded == self.total
+    }
+
+    pub fn fraction(&self) -> f32 {
+        if self.total == 0 {
+            1.0
+        } else {
+            self.loaded as f32 / self.total as f32
+        }
+    }
+
+    pub fn message(&self) -> String {
+        if self.failed.is_empty() {
+            format!("Loading... {}/{}", self.loaded, self.total)
+        } else {
+            format!("Failed to load:\n{}", self.failed.join("\n"))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn failed_assets_are_listed_in_the_message() {
+        let progress = LoadProgress {
+            failed: vec!["models/card.glb".to_string(), "textures/x.png".to_string()],
+            loaded: 3,
+            total: 5,
+        };
+
+        assert!(!progress.done());
+        assert_eq!(progress.fraction(), 0.6);
+        assert_eq!(
+            progress.message(),
+            "Failed to load:\nmodels/card.glb\ntextures/x.png"
+        );
+    }
+}
@@ -16,26 +16,49 @@ use bevy_tweening::{
     TweeningPlugin,
 };
 use rand::Rng;
-use std::time::Duration;
 
+mod achievements;
 mod board;
 mod cards;
 mod deck;
 mod hand;
+mod input;
+mod leaderboard;
+mod loading;
 mod menu;
 mod players;
+mod profile;
+mod puzzle;
+mod settings;
 mod states;
-
+mod stats;
+mod storage;
+#[cfg(test)]
+mod tests;
+mod tutorial;
+mod ui;
+
+use achievements::*;
 use board::*;
 use cards::*;
 use deck::*;
 use hand::*;
+use input::*;
+use leaderboard::*;
+use loading::*;
 use menu::*;
 use players::*;
+use profile::*;
+use puzzle::*;
+use settings::*;
 use states::*;
+use stats::*;
+use tutorial::*;
+use ui::*;
 
 const ATTACK_TARGET_HEIGHT: f32 = 1.0;
 const CAMERA_MENU_OFFSET: Vec3 = Vec3::new(0.0, 9.0, 1.0);
+const DECK_TRANSLATION: Vec3 = Vec3::new(8.0, 0.0, 5.0);
 const HAND_Z: f32 = 6.5;
 const TWEEN_EVENT_REMOVE_PERFORM_ACTION: u64 = 1;
 
@@ -47,121 +70,295 @@ enum PlayCardSystemSet {
 
 fn main() {
     App::new()
-        .add_state::<GameState>()
-        .add_event::<AttackedEvent>()
-        .add_event::<CardPlayedEvent>()
         .insert_resource(Msaa::Sample4)
         .add_plugins(DefaultPlugins)
         .add_plugin(PickingPlugin)
         .add_plugin(InteractablePickingPlugin)
         .add_plugin(TweeningPlugin)
+        .add_plugin(SigilSiegePlugin)
         .insert_resource(ClearColor(Color::rgb(0.06, 0.06, 0.08)))
         .add_startup_system(setup)
-        .add_system(
-            apply_ability::<Opponent, OpponentBoard>.in_set(OnUpdate(GameState::OpponentPlayCards)),
-        )
-        .add_system(apply_ability::<Player, PlayerBoard>.run_if(resource_exists::<PlayerBoard>()))
-        .add_system(apply_damage.in_schedule(OnExit(GameState::OpponentAttacking)))
-        .add_system(apply_damage.in_schedule(OnExit(GameState::PlayerAttacking)))
-        .add_system(
-            attack::<Opponent, OpponentBoard, PlayerBoard, PlayerState>
-                .in_set(OnUpdate(GameState::OpponentAttacking)),
-        )
-        .add_system(
-            attack::<Player, PlayerBoard, OpponentBoard, OpponentState>
-                .in_set(OnUpdate(GameState::PlayerAttacking)),
-        )
-        .add_system(attack_finished::<Opponent>.in_set(OnUpdate(GameState::OpponentAttacking)))
-        .add_system(attack_finished::<Player>.in_set(OnUpdate(GameState::PlayerAttacking)))
-        .add_system(check_lose_condition.run_if(resource_exists::<PlayerState>()))
-        .add_system(check_win_condition.run_if(resource_exists::<OpponentState>()))
-        .add_system(cleanup_game.in_schedule(OnEnter(GameState::StartGame)))
-        .add_system(cleanup_system)
-        .add_system(click_config_button)
-        .add_system(click_play_button)
-        .add_system(draw_cards.in_set(OnUpdate(GameState::PlayerTurn)))
-        .add_system(draw_cards_opponent.in_schedule(OnEnter(GameState::OpponentPlayCards)))
-        .add_system(end_turn.in_set(OnUpdate(GameState::PlayerTurn)))
-        .add_system(end_turn_opponent.in_set(OnUpdate(GameState::OpponentTurn)))
-        .add_system(game_over.in_set(OnUpdate(GameState::Lose)))
-        .add_system(game_over.in_set(OnUpdate(GameState::Win)))
-        .add_system(hover_button)
-        .add_system(hover_card_placeholder.in_set(OnUpdate(GameState::PlayerTurn)))
-        .add_system(hover_dial.in_set(OnUpdate(GameState::PlayerTurn)))
-        .add_system(hover_hand.in_set(OnUpdate(GameState::PlayerTurn)))
-        .add_system(
-            mark_attackers::<OpponentBoard>.in_schedule(OnEnter(GameState::OpponentAttacking)),
-        )
-        .add_system(mark_attackers::<PlayerBoard>.in_schedule(OnEnter(GameState::PlayerAttacking)))
-        .add_system(mark_cards_to_draw.in_schedule(OnEnter(GameState::PlayerTurn)))
-        .add_system(pick_from_hand.in_set(OnUpdate(GameState::PlayerTurn)))
-        .add_system(
-            play_card
-                .in_set(PlayCardSystemSet::PlayCard)
-                .in_set(OnUpdate(GameState::PlayerTurn))
-                .before(PlayCardSystemSet::CardPlayed),
-        )
-        .add_system(play_opponent_cards.in_set(OnUpdate(GameState::OpponentPlayCards)))
-        .add_system(
-            receive_ability::<Opponent, OpponentBoard>
-                .in_set(OnUpdate(GameState::OpponentPlayCards)),
-        )
-        .add_system(receive_ability::<Player, PlayerBoard>.in_set(OnUpdate(GameState::PlayerTurn)))
-        .add_system(
-            remove_killed::<Opponent, OpponentBoard>.run_if(resource_exists::<OpponentBoard>()),
-        )
-        .add_system(remove_killed::<Player, PlayerBoard>.run_if(resource_exists::<PlayerBoard>()))
-        .add_system(remove_perform_action)
-        .add_system(reset_dial.in_schedule(OnEnter(GameState::PlayerTurn)))
-        .add_system(reset_hand.in_schedule(OnEnter(GameState::PlayerTurn)))
-        .add_system(
-            reset_power::<Opponent, OpponentState>
-                .in_schedule(OnEnter(GameState::OpponentPlayCards)),
-        )
-        .add_system(reset_power::<Player, PlayerState>.in_schedule(OnEnter(GameState::PlayerTurn)))
-        .add_system(setup_game.in_set(OnUpdate(GameState::StartGame)))
-        .add_system(
-            slide_hand
-                .in_set(PlayCardSystemSet::CardPlayed)
-                .in_set(OnUpdate(GameState::PlayerTurn)),
-        )
-        .add_system(spend_power.in_set(OnUpdate(GameState::PlayerTurn)))
-        .add_system(update_player_health)
-        .add_system(update_sigils::<Attack, AttackSigil>)
-        .add_system(update_sigils::<Cost, CostSigil>)
-        .add_system(update_sigils::<Health, HealthSigil>)
         .run();
 }
 
+struct SigilSiegePlugin;
+
+impl Plugin for SigilSiegePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_state::<GameState>()
+            .add_event::<AttackedEvent>()
+            .add_event::<CardHitEvent>()
+            .add_event::<CardPlayedEvent>()
+            .add_event::<InputAction>()
+            .add_system(
+                advance_tutorial
+                    .after(focus_input)
+                    .run_if(resource_exists::<Tutorial>()),
+            )
+            .add_system(
+                apply_ability::<Opponent, OpponentBoard>
+                    .before(play_opponent_cards)
+                    .in_set(OnUpdate(GameState::OpponentPlayCards)),
+            )
+            .add_system(
+                apply_ability::<Player, PlayerBoard>.run_if(resource_exists::<PlayerBoard>()),
+            )
+            .add_system(apply_damage.in_schedule(OnExit(GameState::OpponentAttacking)))
+            .add_system(apply_damage.in_schedule(OnExit(GameState::PlayerAttacking)))
+            .add_system(
+                apply_fatigue::<OpponentState>
+                    .before(draw_cards_opponent)
+                    .in_schedule(OnEnter(GameState::OpponentPlayCards)),
+            )
+            .add_system(apply_fatigue::<PlayerState>.in_schedule(OnEnter(GameState::PlayerTurn)))
+            .add_system(
+                attack::<Opponent, OpponentBoard, PlayerBoard, PlayerState>
+                    .in_set(OnUpdate(GameState::OpponentAttacking)),
+            )
+            .add_system(
+                attack::<Player, PlayerBoard, OpponentBoard, OpponentState>
+                    .in_set(OnUpdate(GameState::PlayerAttacking)),
+            )
+            .add_system(attack_finished::<Opponent>.in_set(OnUpdate(GameState::OpponentAttacking)))
+            .add_system(attack_finished::<Player>.in_set(OnUpdate(GameState::PlayerAttacking)))
+            .add_system(cancel_pick.in_set(OnUpdate(GameState::PlayerTurn)))
+            .add_system(check_loading.in_set(OnUpdate(GameState::Loading)))
+            .add_system(check_lose_condition.run_if(resource_exists::<PlayerState>()))
+            .add_system(
+                check_turn_limit
+                    .after(attack_finished::<Opponent>)
+                    .in_set(OnUpdate(GameState::OpponentAttacking)),
+            )
+            .add_system(
+                check_puzzle_goal
+                    .after(attack_finished::<Player>)
+                    .run_if(resource_exists::<ActivePuzzle>())
+                    .in_set(OnUpdate(GameState::PlayerAttacking)),
+            )
+            .add_system(check_win_condition.run_if(resource_exists::<OpponentState>()))
+            .add_system(clear_preview)
+            .add_system(cleanup_game.in_schedule(OnEnter(GameState::StartGame)))
+            .add_system(cleanup_system)
+            .add_system(click_config_button)
+            .add_system(click_daily_button)
+            .add_system(click_deck_out_button)
+            .add_system(
+                click_leaderboard_button
+                    .run_if(resource_exists::<Leaderboard>())
+                    .run_if(resource_exists::<Profile>()),
+            )
+            .add_system(click_play_button)
+            .add_system(click_puzzle_option.in_set(OnUpdate(GameState::Puzzles)))
+            .add_system(click_results_button)
+            .add_system(
+                click_settings_option
+                    .before(edit_player_name)
+                    .before(rebind_input)
+                    .in_set(OnUpdate(GameState::Settings)),
+            )
+            .add_system(click_tutorial_button)
+            .add_system(close_profile.in_set(OnUpdate(GameState::Profile)))
+            .add_system(confirm_mulligan.in_set(OnUpdate(GameState::Mulligan)))
+            .add_system(deal_opening_hand.in_schedule(OnEnter(GameState::Mulligan)))
+            .add_system(despawn_loading_screen.in_schedule(OnExit(GameState::Loading)))
+            .add_system(despawn_profile_screen.in_schedule(OnExit(GameState::Profile)))
+            .add_system(despawn_puzzle_screen.in_schedule(OnExit(GameState::Puzzles)))
+            .add_system(despawn_settings_menu.in_schedule(OnExit(GameState::Settings)))
+            .add_system(draw_cards.in_set(OnUpdate(GameState::Mulligan)))
+            .add_system(draw_cards.in_set(OnUpdate(GameState::PlayerTurn)))
+            .add_system(draw_cards_opponent.in_schedule(OnEnter(GameState::OpponentPlayCards)))
+            .add_system(edit_player_name.in_set(OnUpdate(GameState::Settings)))
+            .add_system(end_turn.in_set(OnUpdate(GameState::PlayerTurn)))
+            .add_system(end_turn_opponent.in_set(OnUpdate(GameState::OpponentTurn)))
+            .add_system(
+                expire_modifiers::<Opponent, OpponentState>
+                    .after(draw_cards_opponent)
+                    .in_schedule(OnEnter(GameState::OpponentPlayCards)),
+            )
+            .add_system(
+                expire_modifiers::<Player, PlayerState>
+                    .after(mark_cards_to_draw)
+                    .in_schedule(OnEnter(GameState::PlayerTurn)),
+            )
+            .add_system(
+                finish_mulligan
+                    .before(confirm_mulligan)
+                    .in_set(OnUpdate(GameState::Mulligan)),
+            )
+            .add_system(focus_input.after(read_input))
+            .add_system(game_over.in_set(OnUpdate(GameState::Draw)))
+            .add_system(game_over.in_set(OnUpdate(GameState::Lose)))
+            .add_system(game_over.in_set(OnUpdate(GameState::Win)))
+            .add_system(hide_game_over_text.in_schedule(OnEnter(GameState::StartGame)))
+            .add_system(hover_button)
+            .add_system(hover_card)
+            .add_system(hover_card_placeholder.in_set(OnUpdate(GameState::PlayerTurn)))
+            .add_system(hover_dial.in_set(OnUpdate(GameState::Mulligan)))
+            .add_system(hover_dial.in_set(OnUpdate(GameState::PlayerTurn)))
+            .add_system(hover_hand.in_set(OnUpdate(GameState::PlayerTurn)))
+            .add_system(
+                mark_attackers::<OpponentBoard>.in_schedule(OnEnter(GameState::OpponentAttacking)),
+            )
+            .add_system(
+                mark_attackers::<PlayerBoard>.in_schedule(OnEnter(GameState::PlayerAttacking)),
+            )
+            .add_system(mark_cards_to_draw.in_schedule(OnEnter(GameState::PlayerTurn)))
+            .add_system(mulligan_opponent.in_schedule(OnEnter(GameState::Mulligan)))
+            .add_system(open_profile)
+            .add_system(open_puzzles)
+            .add_system(open_settings)
+            .add_system(pick_from_hand.in_set(OnUpdate(GameState::PlayerTurn)))
+            .add_system(pick_mulligan.in_set(OnUpdate(GameState::Mulligan)))
+            .add_system(
+                play_card
+                    .in_set(PlayCardSystemSet::PlayCard)
+                    .in_set(OnUpdate(GameState::PlayerTurn))
+                    .before(PlayCardSystemSet::CardPlayed),
+            )
+            .add_system(play_opponent_cards.in_set(OnUpdate(GameState::OpponentPlayCards)))
+            .add_system(read_input)
+            .add_system(preview_placement.in_set(OnUpdate(GameState::PlayerTurn)))
+            .add_system(rebind_input.in_set(OnUpdate(GameState::Settings)))
+            .add_system(
+                record_daily
+                    .run_if(resource_exists::<Leaderboard>())
+                    .run_if(resource_exists::<Profile>())
+                    .in_schedule(OnEnter(GameState::Draw)),
+            )
+            .add_system(
+                record_daily
+                    .run_if(resource_exists::<Leaderboard>())
+                    .run_if(resource_exists::<Profile>())
+                    .in_schedule(OnEnter(GameState::Lose)),
+            )
+            .add_system(
+                record_daily
+                    .run_if(resource_exists::<Leaderboard>())
+                    .run_if(resource_exists::<Profile>())
+                    .in_schedule(OnEnter(GameState::Win)),
+            )
+            .add_system(
+                record_profile
+                    .run_if(resource_exists::<Profile>())
+                    .in_schedule(OnEnter(GameState::Draw)),
+            )
+            .add_system(
+                record_profile
+                    .run_if(resource_exists::<Profile>())
+                    .in_schedule(OnEnter(GameState::Lose)),
+            )
+            .add_system(
+                record_profile
+                    .run_if(resource_exists::<Profile>())
+                    .in_schedule(OnEnter(GameState::Win)),
+            )
+            .add_system(record_stats.run_if(resource_exists::<MatchStats>()))
+            .add_system(
+                receive_ability::<Opponent, OpponentBoard>
+                    .in_set(OnUpdate(GameState::OpponentPlayCards)),
+            )
+            .add_system(
+                receive_ability::<Player, PlayerBoard>.in_set(OnUpdate(GameState::PlayerTurn)),
+            )
+            .add_system(
+                remove_killed::<Opponent, OpponentBoard, OpponentState>
+                    .run_if(resource_exists::<OpponentBoard>()),
+            )
+            .add_system(
+                remove_killed::<Player, PlayerBoard, PlayerState>
+                    .run_if(resource_exists::<PlayerBoard>()),
+            )
+            .add_system(remove_perform_action)
+            .add_system(reset_dial.in_schedule(OnEnter(GameState::PlayerTurn)))
+            .add_system(reset_hand.in_schedule(OnEnter(GameState::PlayerTurn)))
+            .add_system(reset_undo_stack.in_schedule(OnEnter(GameState::PlayerTurn)))
+            .add_system(
+                reset_power::<Opponent, OpponentState>
+                    .in_schedule(OnEnter(GameState::OpponentPlayCards)),
+            )
+            .add_system(
+                reset_power::<Player, PlayerState>.in_schedule(OnEnter(GameState::PlayerTurn)),
+            )
+            .add_system(reshuffle_graveyard.in_schedule(OnExit(GameState::OpponentAttacking)))
+            .add_system(
+                resurrect
+                    .in_set(PlayCardSystemSet::CardPlayed)
+                    .in_set(OnUpdate(GameState::PlayerTurn)),
+            )
+            .add_system(setup_game.in_set(OnUpdate(GameState::StartGame)))
+            .add_system(
+                slide_hand
+                    .in_set(PlayCardSystemSet::CardPlayed)
+                    .in_set(OnUpdate(GameState::PlayerTurn)),
+            )
+            .add_system(spawn_profile_screen.in_schedule(OnEnter(GameState::Profile)))
+            .add_system(spawn_puzzle_screen.in_schedule(OnEnter(GameState::Puzzles)))
+            .add_system(spawn_settings_menu.in_schedule(OnEnter(GameState::Settings)))
+            .add_system(spend_power.in_set(OnUpdate(GameState::PlayerTurn)))
+            .add_system(
+                track_achievements
+                    .run_if(resource_exists::<AchievementProgress>())
+                    .run_if(resource_exists::<Profile>()),
+            )
+            .add_system(track_card_unlocks.run_if(resource_exists::<Profile>()))
+            .add_system(undo_placement.in_set(OnUpdate(GameState::PlayerTurn)))
+            .add_system(update_achievement_toasts)
+            .add_system(
+                update_discard_pile::<Opponent, OpponentState>
+                    .run_if(resource_exists::<OpponentState>()),
+            )
+            .add_system(
+                update_discard_pile::<Player, PlayerState>.run_if(resource_exists::<PlayerState>()),
+            )
+            .add_system(update_focus_cursor)
+            .add_system(update_forecast.run_if(resource_exists::<PlayerBoard>()))
+            .add_system(update_deck_out_button)
+            .add_system(update_menu_buttons)
+            .add_system(update_opponent_hand.run_if(resource_exists::<OpponentState>()))
+            .add_system(update_phase_banner)
+            .add_system(update_player_health)
+            .add_system(update_settings_menu.in_set(OnUpdate(GameState::Settings)))
+            .add_system(update_sigils::<Attack, AttackSigil>)
+            .add_system(update_sigils::<Cost, CostSigil>)
+            .add_system(update_sigils::<Health, HealthSigil>)
+            .add_system(update_stats_panel)
+            .add_system(update_thinking_indicator)
+            .add_system(update_tooltip)
+            .add_system(update_tutorial);
+    }
+}
+
 fn setup(
     mut commands: Commands,
     asset_server: Res<AssetServer>,
     mut materials: ResMut<Assets<StandardMaterial>>,
+    mut meshes: ResMut<Assets<Mesh>>,
 ) {
-    let arrow_mesh = asset_server.load("models/arrow.glb#Mesh0/Primitive0");
+    let mut loading = LoadingAssets::default();
+    let arrow_mesh = loading.load(&asset_server, "models/arrow.glb#Mesh0/Primitive0");
     let arrow_material = materials.add(StandardMaterial {
         base_color: Color::rgb(0.45, 0.11, 0.15),
         ..default()
     });
-    let block_mesh = asset_server.load("models/block.glb#Mesh0/Primitive0");
+    let block_mesh = loading.load(&asset_server, "models/block.glb#Mesh0/Primitive0");
     let block_material = materials.add(StandardMaterial {
         base_color: Color::WHITE,
-        base_color_texture: Some(asset_server.load("textures/stone-base-color.png")),
+        base_color_texture: Some(loading.load(&asset_server, "textures/stone-base-color.png")),
         perceptual_roughness: 1.0,
         reflectance: 0.0,
         ..default()
     });
-    let dial_mesh = asset_server.load("models/dial.glb#Mesh0/Primitive0");
+    let dial_mesh = loading.load(&asset_server, "models/dial.glb#Mesh0/Primitive0");
     let dial_material = materials.add(StandardMaterial {
         base_color: Color::rgb(0.12, 0.12, 0.12),
         metallic: 1.0,
         perceptual_roughness: 0.2,
         ..default()
     });
-    let mesh = asset_server.load("models/board.glb#Mesh0/Primitive0");
+    let mesh = loading.load(&asset_server, "models/board.glb#Mesh0/Primitive0");
     let material = materials.add(StandardMaterial {
         base_color: Color::WHITE,
-        base_color_texture: Some(asset_server.load("textures/board-base-color.png")),
+        base_color_texture: Some(loading.load(&asset_server, "textures/board-base-color.png")),
         perceptual_roughness: 1.0,
         reflectance: 0.0,
         ..default()
@@ -188,20 +385,38 @@ fn setup(
         invisable: invisable_material.clone(),
         hovered: hovered_material,
     });
+    commands.insert_resource(PreviewMaterials {
+        buffed: materials.add(StandardMaterial {
+            base_color: Color::rgb(0.2, 0.8, 0.3),
+            unlit: true,
+            ..default()
+        }),
+        buffed_by: materials.add(StandardMaterial {
+            base_color: Color::rgb(0.2, 0.5, 0.9),
+            unlit: true,
+            ..default()
+        }),
+        facing: materials.add(StandardMaterial {
+            base_color: Color::rgb(0.9, 0.2, 0.2),
+            unlit: true,
+            ..default()
+        }),
+    });
 
-    let card_mesh = asset_server.load("models/card.glb#Mesh0/Primitive0");
+    let card_mesh = loading.load(&asset_server, "models/card.glb#Mesh0/Primitive0");
     let card_material = materials.add(StandardMaterial {
         base_color: Color::WHITE,
-        base_color_texture: Some(asset_server.load("textures/card-base-color.png")),
+        base_color_texture: Some(loading.load(&asset_server, "textures/card-base-color.png")),
         reflectance: 0.0,
         ..default()
     });
 
-    let heart_mesh = asset_server.load("models/heart.glb#Mesh0/Primitive0");
-    let gem_mesh = asset_server.load("models/gem.glb#Mesh0/Primitive0");
-    let pitchfork_mesh = asset_server.load("models/pitchfork.glb#Mesh0/Primitive0");
-    let sword_mesh = asset_server.load("models/sword.glb#Mesh0/Primitive0");
-    let tower_mesh = asset_server.load("models/tower.glb#Mesh0/Primitive0");
+    let heart_mesh = loading.load(&asset_server, "models/heart.glb#Mesh0/Primitive0");
+    let gem_mesh = loading.load(&asset_server, "models/gem.glb#Mesh0/Primitive0");
+    let phoenix_mesh = meshes.add(phoenix_mesh());
+    let pitchfork_mesh = loading.load(&asset_server, "models/pitchfork.glb#Mesh0/Primitive0");
+    let sword_mesh = loading.load(&asset_server, "models/sword.glb#Mesh0/Primitive0");
+    let tower_mesh = loading.load(&asset_server, "models/tower.glb#Mesh0/Primitive0");
     let heart_material = materials.add(StandardMaterial {
         base_color: Color::RED,
         metallic: 1.0,
@@ -219,6 +434,13 @@ fn setup(
         perceptual_roughness: 0.0,
         ..default()
     });
+    let phoenix_material = materials.add(StandardMaterial {
+        base_color: Color::ORANGE_RED,
+        emissive: Color::ORANGE,
+        metallic: 1.0,
+        perceptual_roughness: 0.0,
+        ..default()
+    });
     let gem_empty_material = materials.add(StandardMaterial {
         base_color: Color::GRAY,
         metallic: 1.0,
@@ -226,6 +448,32 @@ fn setup(
         ..default()
     });
 
+    commands.spawn((
+        PbrBundle {
+            mesh: gem_mesh.clone(),
+            material: gem_material.clone(),
+            transform: Transform::from_scale(Vec3::splat(1.8)),
+            visibility: Visibility::Hidden,
+            ..default()
+        },
+        FocusCursor,
+    ));
+    commands.spawn((
+        PbrBundle {
+            mesh: gem_mesh.clone(),
+            material: heart_material.clone(),
+            transform: Transform::from_scale(Vec3::splat(2.4)),
+            visibility: Visibility::Hidden,
+            ..default()
+        },
+        TutorialHighlight,
+    ));
+    commands.insert_resource(EditingName::default());
+    commands.insert_resource(Focus::default());
+    commands.insert_resource(Leaderboard::load());
+    commands.insert_resource(Rebinding::default());
+    commands.insert_resource(Profile::load());
+    commands.insert_resource(Settings::load());
     commands.insert_resource(CardAssets {
         card_mesh: card_mesh.clone(),
         card_material,
@@ -234,12 +482,19 @@ fn setup(
         gem_empty_material,
         gem_mesh,
         gem_material,
+        phoenix_material,
+        phoenix_mesh,
         pitchfork_mesh,
         sword_mesh,
         tower_mesh,
         black_material,
     });
-    commands.insert_resource(OpponentState::default().with_deck_size(20).with_health(20));
+    commands.insert_resource(MenuSelection::Small.game_config());
+    commands.insert_resource(
+        OpponentState::default()
+            .with_deck_size(20, rand::random(), &CardType::all())
+            .with_health(20),
+    );
     commands.insert_resource(PlayerState::default());
 
     let menu_translation = Vec3::new(50.0, 0.0, 50.0);
@@ -264,10 +519,15 @@ fn setup(
         ))
         .with_children(|parent| {
             parent.spawn(PbrBundle {
-                mesh: asset_server.load("models/sigil-siege-text.glb#Mesh0/Primitive0"),
+                mesh: loading.load(
+                    &asset_server,
+                    "models/sigil-siege-text.glb#Mesh0/Primitive0",
+                ),
                 material: materials.add(StandardMaterial {
                     base_color: Color::WHITE,
-                    base_color_texture: Some(asset_server.load("textures/text-base-color.png")),
+                    base_color_texture: Some(
+                        loading.load(&asset_server, "textures/text-base-color.png"),
+                    ),
                     perceptual_roughness: 1.0,
                     unlit: true,
                     ..default()
@@ -281,7 +541,7 @@ fn setup(
                 Button,
                 PickableBundle::default(),
                 PbrBundle {
-                    mesh: asset_server.load("models/small-btn.glb#Mesh0/Primitive0"),
+                    mesh: loading.load(&asset_server, "models/small-btn.glb#Mesh0/Primitive0"),
                     material: button_material_active.clone(),
                     ..default()
                 },
@@ -292,7 +552,7 @@ fn setup(
                 Button,
                 PickableBundle::default(),
                 PbrBundle {
-                    mesh: asset_server.load("models/medium-btn.glb#Mesh0/Primitive0"),
+                    mesh: loading.load(&asset_server, "models/medium-btn.glb#Mesh0/Primitive0"),
                     material: button_material.clone(),
                     ..default()
                 },
@@ -303,7 +563,7 @@ fn setup(
                 Button,
                 PickableBundle::default(),
                 PbrBundle {
-                    mesh: asset_server.load("models/large-btn.glb#Mesh0/Primitive0"),
+                    mesh: loading.load(&asset_server, "models/large-btn.glb#Mesh0/Primitive0"),
                     material: button_material.clone(),
                     ..default()
                 },
@@ -314,16 +574,25 @@ fn setup(
                 Button,
                 PickableBundle::default(),
                 PbrBundle {
-                    mesh: asset_server.load("models/play-btn.glb#Mesh0/Primitive0"),
+                    mesh: loading.load(&asset_server, "models/play-btn.glb#Mesh0/Primitive0"),
                     material: button_material.clone(),
                     ..default()
                 },
             ));
 
+            parent.spawn((
+                GameOverText::Draw,
+                PbrBundle {
+                    mesh: loading.load(&asset_server, "models/draw-text.glb#Mesh0/Primitive0"),
+                    visibility: Visibility::Hidden,
+                    ..default()
+                },
+            ));
+
             parent.spawn((
                 GameOverText::Lose,
                 PbrBundle {
-                    mesh: asset_server.load("models/you-lost-text.glb#Mesh0/Primitive0"),
+                    mesh: loading.load(&asset_server, "models/you-lost-text.glb#Mesh0/Primitive0"),
                     visibility: Visibility::Hidden,
                     ..default()
                 },
@@ -332,13 +601,300 @@ fn setup(
             parent.spawn((
                 GameOverText::Win,
                 PbrBundle {
-                    mesh: asset_server.load("models/you-won-text.glb#Mesh0/Primitive0"),
+                    mesh: loading.load(&asset_server, "models/you-won-text.glb#Mesh0/Primitive0"),
+                    visibility: Visibility::Hidden,
+                    ..default()
+                },
+            ));
+        });
+
+    let ui_assets = UiAssets {
+        font: loading.load(&asset_server, "fonts/DejaVuSans.ttf"),
+    };
+
+    commands
+        .spawn((
+            NodeBundle {
+                background_color: Color::rgba(0.0, 0.0, 0.0, 0.75).into(),
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    position: UiRect {
+                        bottom: Val::Px(16.0),
+                        right: Val::Px(16.0),
+                        ..default()
+                    },
+                    padding: UiRect::all(Val::Px(12.0)),
+                    ..default()
+                },
+                visibility: Visibility::Hidden,
+                ..default()
+            },
+            Tooltip::default(),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                TextBundle::from_section(
+                    "",
+                    TextStyle {
+                        font: ui_assets.font.clone(),
+                        font_size: 20.0,
+                        color: Color::WHITE,
+                    },
+                ),
+                TooltipText,
+            ));
+        });
+
+    commands.spawn((
+        TextBundle {
+            background_color: Color::rgba(0.0, 0.0, 0.0, 0.75).into(),
+            visibility: Visibility::Hidden,
+            ..TextBundle::from_section(
+                "",
+                TextStyle {
+                    font: ui_assets.font.clone(),
+                    font_size: 20.0,
+                    color: Color::WHITE,
+                },
+            )
+            .with_style(Style {
+                position_type: PositionType::Absolute,
+                position: UiRect {
+                    top: Val::Px(16.0),
+                    right: Val::Px(16.0),
+                    ..default()
+                },
+                padding: UiRect::all(Val::Px(12.0)),
+                ..default()
+            })
+        },
+        ForecastPanel::default(),
+    ));
+
+    commands.spawn((
+        TextBundle {
+            background_color: Color::rgba(0.0, 0.0, 0.0, 0.75).into(),
+            visibility: Visibility::Hidden,
+            ..TextBundle::from_section(
+                "",
+                TextStyle {
+                    font: ui_assets.font.clone(),
+                    font_size: 20.0,
+                    color: Color::WHITE,
+                },
+            )
+            .with_style(Style {
+                position_type: PositionType::Absolute,
+                position: UiRect {
+                    top: Val::Px(16.0),
+                    left: Val::Px(16.0),
+                    ..default()
+                },
+                padding: UiRect::all(Val::Px(12.0)),
+                ..default()
+            })
+        },
+        StatsPanel,
+    ));
+
+    commands
+        .spawn(NodeBundle {
+            style: Style {
+                justify_content: JustifyContent::Center,
+                position_type: PositionType::Absolute,
+                position: UiRect {
+                    top: Val::Percent(20.0),
+                    ..default()
+                },
+                size: Size::width(Val::Percent(100.0)),
+                ..default()
+            },
+            ..default()
+        })
+        .with_children(|parent| {
+            parent.spawn((
+                TextBundle {
+                    background_color: Color::rgba(0.0, 0.0, 0.0, 0.75).into(),
+                    visibility: Visibility::Hidden,
+                    ..TextBundle::from_section(
+                        "",
+                        TextStyle {
+                            font: ui_assets.font.clone(),
+                            font_size: 48.0,
+                            color: Color::WHITE,
+                        },
+                    )
+                    .with_style(Style {
+                        padding: UiRect::all(Val::Px(16.0)),
+                        ..default()
+                    })
+                },
+                PhaseBanner::default(),
+            ));
+        });
+
+    commands
+        .spawn(NodeBundle {
+            style: Style {
+                justify_content: JustifyContent::Center,
+                position_type: PositionType::Absolute,
+                position: UiRect {
+                    bottom: Val::Px(16.0),
+                    ..default()
+                },
+                size: Size::width(Val::Percent(100.0)),
+                ..default()
+            },
+            ..default()
+        })
+        .with_children(|parent| {
+            parent.spawn((
+                TextBundle {
                     visibility: Visibility::Hidden,
+                    ..TextBundle::from_section(
+                        "",
+                        TextStyle {
+                            font: ui_assets.font.clone(),
+                            font_size: 24.0,
+                            color: Color::WHITE,
+                        },
+                    )
+                },
+                ThinkingIndicator,
+            ));
+        });
+
+    spawn_menu_button(
+        &mut commands,
+        &ui_assets,
+        "Settings (F1)",
+        16.0,
+        SettingsButton,
+    );
+    spawn_menu_button(&mut commands, &ui_assets, "Profile", 48.0, ProfileButton);
+    spawn_menu_button(
+        &mut commands,
+        &ui_assets,
+        "Daily challenge",
+        80.0,
+        DailyButton,
+    );
+    spawn_menu_button(&mut commands, &ui_assets, "Puzzles", 112.0, PuzzleButton);
+    spawn_menu_button(&mut commands, &ui_assets, "Tutorial", 144.0, TutorialButton);
+    spawn_menu_button(&mut commands, &ui_assets, "", 176.0, DeckOutButton);
+
+    commands
+        .spawn(NodeBundle {
+            style: Style {
+                justify_content: JustifyContent::Center,
+                position_type: PositionType::Absolute,
+                position: UiRect {
+                    top: Val::Px(16.0),
+                    ..default()
+                },
+                size: Size::width(Val::Percent(100.0)),
+                ..default()
+            },
+            ..default()
+        })
+        .with_children(|parent| {
+            let style = TextStyle {
+                font: ui_assets.font.clone(),
+                font_size: 20.0,
+                color: Color::WHITE,
+            };
+
+            parent
+                .spawn((
+                    NodeBundle {
+                        background_color: Color::rgba(0.0, 0.0, 0.0, 0.75).into(),
+                        style: Style {
+                            flex_direction: FlexDirection::Column,
+                            padding: UiRect::all(Val::Px(12.0)),
+                            max_size: Size::width(Val::Px(480.0)),
+                            ..default()
+                        },
+                        visibility: Visibility::Hidden,
+                        ..default()
+                    },
+                    TutorialPanel,
+                ))
+                .with_children(|parent| {
+                    parent.spawn((TextBundle::from_section("", style.clone()), TutorialText));
+                    parent.spawn((
+                        TextBundle::from_section("Next", style.clone()).with_style(Style {
+                            margin: UiRect::top(Val::Px(12.0)),
+                            ..default()
+                        }),
+                        Interaction::default(),
+                        TutorialOption::Next,
+                    ));
+                    parent.spawn((
+                        TextBundle::from_section("Skip tutorial", style).with_style(Style {
+                            margin: UiRect::top(Val::Px(8.0)),
+                            ..default()
+                        }),
+                        Interaction::default(),
+                        TutorialOption::Skip,
+                    ));
+                });
+        });
+
+    commands
+        .spawn((
+            NodeBundle {
+                background_color: Color::BLACK.into(),
+                style: Style {
+                    flex_direction: FlexDirection::Column,
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    position_type: PositionType::Absolute,
+                    size: Size::new(Val::Percent(100.0), Val::Percent(100.0)),
                     ..default()
                 },
+                z_index: ZIndex::Global(20),
+                ..default()
+            },
+            LoadingScreen,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                TextBundle::from_section(
+                    "Loading...",
+                    TextStyle {
+                        font: ui_assets.font.clone(),
+                        font_size: 24.0,
+                        color: Color::WHITE,
+                    },
+                ),
+                LoadingText,
             ));
+            parent
+                .spawn(NodeBundle {
+                    background_color: Color::DARK_GRAY.into(),
+                    style: Style {
+                        margin: UiRect::all(Val::Px(16.0)),
+                        size: Size::new(Val::Px(320.0), Val::Px(12.0)),
+                        ..default()
+                    },
+                    ..default()
+                })
+                .with_children(|parent| {
+                    parent.spawn((
+                        NodeBundle {
+                            background_color: Color::WHITE.into(),
+                            style: Style {
+                                size: Size::new(Val::Percent(0.0), Val::Percent(100.0)),
+                                ..default()
+                            },
+                            ..default()
+                        },
+                        LoadingBar,
+                    ));
+                });
         });
 
+    commands.insert_resource(ui_assets);
     commands.insert_resource(MenuMaterials {
         button_material,
         button_material_active,
@@ -431,36 +987,121 @@ fn setup(
             }
         }
     }
+
+    commands.insert_resource(loading);
+}
+
+fn advance_tutorial(
+    current_state: Res<State<GameState>>,
+    board: Option<Res<PlayerBoard>>,
+    mut tutorial: ResMut<Tutorial>,
+    mut ev_action: EventReader<InputAction>,
+    mut ev_picking: ResMut<Events<PickingEvent>>,
+    q_option: Query<(&Interaction, &TutorialOption), Changed<Interaction>>,
+    q_picked: Query<With<Picked>>,
+    q_hand: Query<(Entity, &Hand)>,
+    q_placeholder: Query<(Entity, &CardPlaceholder), With<Player>>,
+    q_dial: Query<Entity, With<TurnDial>>,
+    q_power: Query<(Entity, &Power), With<Player>>,
+) {
+    let mut next = ev_action
+        .iter()
+        .any(|action| *action == InputAction::Confirm);
+
+    for (interaction, option) in q_option.iter() {
+        if *interaction != Interaction::Clicked {
+            continue;
+        }
+
+        match option {
+            TutorialOption::Next => next = true,
+            TutorialOption::Skip => tutorial.finish(),
+        }
+    }
+
+    if matches!(
+        current_state.0,
+        GameState::Win | GameState::Lose | GameState::Draw
+    ) {
+        tutorial.finish();
+    }
+
+    let step = if let Some(step) = tutorial.current() {
+        step
+    } else {
+        return;
+    };
+
+    let done = match step.until {
+        TutorialGoal::CardPicked => !q_picked.is_empty(),
+        TutorialGoal::CardPlaced(index) => board.map_or(false, |board| !board.unoccupied(index)),
+        TutorialGoal::Continue => next,
+        TutorialGoal::Phase(phase) => current_state.0 == phase,
+    };
+
+    let target = match step.highlight {
+        Highlight::Dial => q_dial.get_single().ok(),
+        Highlight::HandCard(index) => q_hand
+            .iter()
+            .find(|(_, hand)| hand.0 == index)
+            .map(|(entity, _)| entity),
+        Highlight::None => None,
+        Highlight::Placeholder(index) => q_placeholder
+            .iter()
+            .find(|(_, placeholder)| placeholder.0 == index)
+            .map(|(entity, _)| entity),
+        Highlight::Power(index) => q_power
+            .iter()
+            .find(|(_, power)| power.index == index)
+            .map(|(entity, _)| entity),
+    };
+
+    if done {
+        // Confirm also clicks whatever is focused, so drop those clicks before
+        // they reach the next step's target.
+        ev_picking.clear();
+        tutorial.advance();
+    } else {
+        tutorial.target = target;
+    }
 }
 
 fn apply_ability<C: Component, B: Board>(
     board: Res<B>,
     mut ev_played: EventReader<CardPlayedEvent>,
-    mut q_cards: Query<(&CardType, &mut Attack, &mut Health), With<C>>,
+    q_source: Query<&CardType, With<C>>,
+    mut q_cards: Query<(&CardType, &mut StatModifiers, &mut Attack, &mut Health), With<C>>,
 ) {
     for ev in ev_played.iter() {
-        let (affects, effect) = if let Ok((card_type, _, _)) = q_cards.get_mut(ev.entity) {
-            (
-                Some(card_type.affects(ev.entity, board.state())),
-                Some(card_type.effect()),
-            )
-        } else {
-            (None, None)
-        };
+        if let Ok(card_type) = q_source.get(ev.entity) {
+            let effect = card_type.effect();
 
-        if let (Some(affects), Some(effect)) = (affects, effect) {
-            for entity in affects {
-                if let Ok((_, mut attack, mut health)) = q_cards.get_mut(entity) {
-                    effect.apply(&mut attack, &mut health);
+            for entity in card_type.affects(ev.entity, board.state()) {
+                if let Ok((target_type, mut modifiers, mut attack, mut health)) =
+                    q_cards.get_mut(entity)
+                {
+                    modifiers.add(ev.entity, effect);
+                    modifiers.sync(*target_type, &mut attack, &mut health);
                 }
             }
         }
     }
 }
 
-fn apply_damage(mut commands: Commands, mut q_damage: Query<(Entity, &Damage, &mut Health)>) {
-    for (entity, damage, mut health) in q_damage.iter_mut() {
-        health.0 -= damage.0;
+fn apply_damage(
+    mut commands: Commands,
+    mut q_damage: Query<(
+        Entity,
+        &CardType,
+        &Damage,
+        &mut StatModifiers,
+        &mut Attack,
+        &mut Health,
+    )>,
+) {
+    for (entity, card_type, damage, mut modifiers, mut attack, mut health) in q_damage.iter_mut() {
+        modifiers.take_damage(damage.0);
+        modifiers.sync(*card_type, &mut attack, &mut health);
         commands.entity(entity).remove::<Damage>();
 
         if health.0 <= 0 {
@@ -469,12 +1110,31 @@ fn apply_damage(mut commands: Commands, mut q_damage: Query<(Entity, &Damage, &m
     }
 }
 
+fn apply_fatigue<S: PlayableState>(
+    config: Res<GameConfig>,
+    mut player_state: ResMut<S>,
+    mut ev_attacked: EventWriter<AttackedEvent>,
+) {
+    // Both sides are checked before they draw, so fatigue starts on the first
+    // turn with nothing left to draw.
+    if config.deck_out != DeckOutRule::Fatigue || player_state.deck_size() > 0 {
+        return;
+    }
+
+    let damage = player_state.fatigue();
+
+    player_state.take_damage(damage as i32);
+    ev_attacked.send(S::attacked_event(damage, DamageSource::Fatigue));
+}
+
 fn attack<C: Component, A: Board, B: Board, S: PlayableState>(
     mut commands: Commands,
+    settings: Res<Settings>,
     attacking: Res<A>,
     attacked: Res<B>,
     mut player_state: ResMut<S>,
     mut ev_attacked: EventWriter<AttackedEvent>,
+    mut ev_hit: EventWriter<CardHitEvent>,
     q_attacking: Query<(Entity, &Attack, &Transform), (With<Attacker>, With<C>)>,
     q_attacked: Query<&Transform, (Without<AttackTarget>, Without<C>)>,
     q_target: Query<&Transform, (With<AttackTarget>, Without<Attacker>, Without<C>)>,
@@ -488,14 +1148,31 @@ fn attack<C: Component, A: Board, B: Board, S: PlayableState>(
             q_attacked.get(across.entity).unwrap().translation
         } else {
             player_state.take_damage(attack);
-            ev_attacked.send(S::attacked_event(attack.max(0) as u32));
+            ev_attacked.send(S::attacked_event(
+                attack.max(0) as u32,
+                DamageSource::Attack,
+            ));
 
             q_target.get_single().unwrap().translation
         };
 
+        ev_hit.send(CardHitEvent {
+            attacker: entity,
+            damage: attack.max(0) as u32,
+        });
+        commands.entity(entity).remove::<Attacker>();
+
+        let durations = settings
+            .animation_duration(200)
+            .zip(settings.animation_duration(500));
+        let (attack_duration, return_duration) = if let Some(durations) = durations {
+            durations
+        } else {
+            continue;
+        };
         let attack_tween = Tween::new(
             EaseFunction::QuadraticIn,
-            Duration::from_millis(200),
+            attack_duration,
             TransformPositionLens {
                 start: transform.translation,
                 end: target,
@@ -503,7 +1180,7 @@ fn attack<C: Component, A: Board, B: Board, S: PlayableState>(
         );
         let return_tween = Tween::new(
             EaseFunction::QuadraticOut,
-            Duration::from_millis(500),
+            return_duration,
             TransformPositionLens {
                 start: target,
                 end: transform.translation,
@@ -511,7 +1188,7 @@ fn attack<C: Component, A: Board, B: Board, S: PlayableState>(
         )
         .with_completed_event(TWEEN_EVENT_REMOVE_PERFORM_ACTION);
 
-        commands.entity(entity).remove::<Attacker>().insert((
+        commands.entity(entity).insert((
             Animator::new(attack_tween.then(return_tween)),
             PerformingAction,
         ));
@@ -530,6 +1207,61 @@ fn attack_finished<C: Component>(
     }
 }
 
+fn cancel_pick(
+    mut commands: Commands,
+    tutorial: Option<Res<Tutorial>>,
+    mut ev_action: EventReader<InputAction>,
+    mut q_picked: Query<(Entity, &mut Transform), With<Picked>>,
+) {
+    if !ev_action
+        .iter()
+        .any(|action| *action == InputAction::Cancel)
+        || tutorial.map_or(false, |tutorial| tutorial.locked())
+    {
+        return;
+    }
+
+    for (entity, mut transform) in q_picked.iter_mut() {
+        transform.translation.z += 1.0;
+        transform.translation.y = CARD_HALF_THICKNESS;
+        commands.entity(entity).remove::<Picked>();
+    }
+}
+
+fn check_loading(
+    asset_server: Res<AssetServer>,
+    loading: Res<LoadingAssets>,
+    mut reported: Local<usize>,
+    mut state: ResMut<NextState<GameState>>,
+    mut q_text: Query<&mut Text, With<LoadingText>>,
+    mut q_bar: Query<(&mut Style, &mut BackgroundColor), With<LoadingBar>>,
+) {
+    let progress = loading.progress(&asset_server);
+
+    if progress.done() {
+        state.set(GameState::Setup);
+
+        return;
+    }
+
+    if progress.failed.len() > *reported {
+        *reported = progress.failed.len();
+        error!("{}", progress.message());
+    }
+
+    for mut text in q_text.iter_mut() {
+        text.sections[0].value = progress.message();
+    }
+
+    for (mut style, mut color) in q_bar.iter_mut() {
+        style.size.width = Val::Percent(progress.fraction() * 100.0);
+
+        if !progress.failed.is_empty() {
+            *color = Color::RED.into();
+        }
+    }
+}
+
 fn check_lose_condition(
     mut ev_attacked: EventReader<AttackedEvent>,
     player_state: Res<PlayerState>,
@@ -539,44 +1271,104 @@ fn check_lose_condition(
     for _ in ev_attacked.iter() {
         if player_state.get_health() <= 0 {
             state.set(GameState::Lose);
-
-            for (text, mut visibility) in q_text.iter_mut() {
-                match text {
-                    GameOverText::Lose => *visibility = Visibility::Visible,
-                    GameOverText::Win => *visibility = Visibility::Hidden,
-                }
-            }
+            show_game_over_text(GameOverText::Lose, &mut q_text);
 
             break;
         }
     }
 }
 
-fn check_win_condition(
-    mut ev_attacked: EventReader<AttackedEvent>,
+fn check_puzzle_goal(
+    puzzle: Res<ActivePuzzle>,
     opponent_state: Res<OpponentState>,
     mut state: ResMut<NextState<GameState>>,
+    q_attacker: Query<(With<Attacker>, With<Player>)>,
+    q_perform_action: Query<(With<PerformingAction>, With<Player>)>,
     mut q_text: Query<(&GameOverText, &mut Visibility), (Without<Camera>, Without<Menu>)>,
 ) {
-    for _ in ev_attacked.iter() {
-        if opponent_state.get_health() <= 0 {
-            state.set(GameState::Win);
+    if q_attacker.iter().next().is_some() || q_perform_action.iter().next().is_some() {
+        return;
+    }
 
-            for (text, mut visibility) in q_text.iter_mut() {
-                match text {
-                    GameOverText::Lose => *visibility = Visibility::Hidden,
-                    GameOverText::Win => *visibility = Visibility::Visible,
-                }
+    match puzzle.0.goal {
+        PuzzleGoal::WinThisTurn => {
+            if opponent_state.get_health() > 0 {
+                state.set(GameState::Lose);
+                show_game_over_text(GameOverText::Lose, &mut q_text);
             }
-
-            break;
         }
     }
 }
 
-fn cleanup_game(
-    mut commands: Commands,
-    q_attack_target: Query<Entity, With<AttackTarget>>,
+fn check_win_condition(
+    mut ev_attacked: EventReader<AttackedEvent>,
+    opponent_state: Res<OpponentState>,
+    mut state: ResMut<NextState<GameState>>,
+    mut q_text: Query<(&GameOverText, &mut Visibility), (Without<Camera>, Without<Menu>)>,
+) {
+    for _ in ev_attacked.iter() {
+        if opponent_state.get_health() <= 0 {
+            state.set(GameState::Win);
+            show_game_over_text(GameOverText::Win, &mut q_text);
+
+            break;
+        }
+    }
+}
+
+fn check_turn_limit(
+    config: Res<GameConfig>,
+    opponent_state: Res<OpponentState>,
+    player_state: Res<PlayerState>,
+    mut state: ResMut<NextState<GameState>>,
+    q_attacker: Query<(With<Attacker>, With<Opponent>)>,
+    q_perform_action: Query<(With<PerformingAction>, With<Opponent>)>,
+    mut q_text: Query<(&GameOverText, &mut Visibility), (Without<Camera>, Without<Menu>)>,
+) {
+    let max_turns = if let Some(max_turns) = config.max_turns {
+        max_turns
+    } else {
+        return;
+    };
+
+    if opponent_state.turn < max_turns
+        || q_attacker.iter().next().is_some()
+        || q_perform_action.iter().next().is_some()
+    {
+        return;
+    }
+
+    // Compare remaining health as a fraction of starting health without going through floats.
+    let player_score = player_state.get_health().max(0) * opponent_state.get_max_health();
+    let opponent_score = opponent_state.get_health().max(0) * player_state.get_max_health();
+    let (outcome, text) = match player_score.cmp(&opponent_score) {
+        std::cmp::Ordering::Greater => (GameState::Win, GameOverText::Win),
+        std::cmp::Ordering::Less => (GameState::Lose, GameOverText::Lose),
+        std::cmp::Ordering::Equal => (GameState::Draw, GameOverText::Draw),
+    };
+
+    state.set(outcome);
+    show_game_over_text(text, &mut q_text);
+}
+
+fn clear_preview(
+    mut commands: Commands,
+    state: Res<State<GameState>>,
+    q_picked: Query<With<Picked>>,
+    q_preview: Query<Entity, With<Preview>>,
+) {
+    if state.0 == GameState::PlayerTurn && !q_picked.is_empty() {
+        return;
+    }
+
+    for entity in q_preview.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+fn cleanup_game(
+    mut commands: Commands,
+    q_attack_target: Query<Entity, With<AttackTarget>>,
     q_card: Query<Entity, (With<CardType>, Without<AttackTarget>)>,
     q_deck: Query<Entity, (With<Deck>, Without<AttackTarget>, Without<CardType>)>,
     q_health: Query<
@@ -598,6 +1390,7 @@ fn cleanup_game(
             Without<PlayerHealth>,
         ),
     >,
+    q_results: Query<Entity, With<ResultsScreen>>,
 ) {
     for entity in q_attack_target.iter() {
         commands.entity(entity).despawn_recursive();
@@ -618,6 +1411,10 @@ fn cleanup_game(
     for entity in q_power.iter() {
         commands.entity(entity).despawn_recursive();
     }
+
+    for entity in q_results.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
 }
 
 fn cleanup_system(mut commands: Commands, mut q_cleanup: Query<(Entity, &CleanUp)>) {
@@ -629,6 +1426,7 @@ fn cleanup_system(mut commands: Commands, mut q_cleanup: Query<(Entity, &CleanUp
 fn click_config_button(
     mut commands: Commands,
     materials: Res<MenuMaterials>,
+    current_state: Res<State<GameState>>,
     mut ev_pick: EventReader<PickingEvent>,
     mut q_active: Query<(Entity, &mut Handle<StandardMaterial>), With<ActiveSelection>>,
     mut q_config: Query<
@@ -636,6 +1434,12 @@ fn click_config_button(
         (With<MenuSelection>, Without<ActiveSelection>),
     >,
 ) {
+    // Clicks landing on an overlay screen must not reach the menu underneath it.
+    if current_state.0.is_overlay() {
+        ev_pick.clear();
+        return;
+    }
+
     for ev in ev_pick.iter() {
         if let PickingEvent::Clicked(e) = ev {
             if let Ok((entity, mut material)) = q_config.get_mut(*e) {
@@ -651,31 +1455,114 @@ fn click_config_button(
     }
 }
 
+fn click_daily_button(
+    mut commands: Commands,
+    profile: Option<Res<Profile>>,
+    settings: Res<Settings>,
+    current_state: Res<State<GameState>>,
+    player: Option<Res<PlayerState>>,
+    mut state: ResMut<NextState<GameState>>,
+    q_button: Query<&Interaction, With<DailyButton>>,
+    mut q_camera: Query<&mut Transform, With<Camera>>,
+) {
+    let in_menu = menu_available(current_state.0, player.as_deref());
+
+    for interaction in q_button.iter() {
+        if in_menu && *interaction == Interaction::Clicked {
+            new_game(&mut commands, daily_config(today()), profile.as_deref());
+
+            let mut transform = q_camera.single_mut();
+
+            *transform = Transform::from_translation(settings.camera_offset())
+                .looking_at(Vec3::ZERO, Vec3::Y);
+
+            state.set(GameState::StartGame);
+        }
+    }
+}
+
+fn click_deck_out_button(
+    current_state: Res<State<GameState>>,
+    player: Option<Res<PlayerState>>,
+    mut settings: ResMut<Settings>,
+    q_button: Query<&Interaction, (With<DeckOutButton>, Changed<Interaction>)>,
+) {
+    let in_menu = menu_available(current_state.0, player.as_deref());
+
+    for interaction in q_button.iter() {
+        if in_menu && *interaction == Interaction::Clicked {
+            settings.cycle(SettingsOption::DeckOut);
+            settings.save();
+        }
+    }
+}
+
+fn click_leaderboard_button(
+    config: Res<GameConfig>,
+    profile: Res<Profile>,
+    mut leaderboard: ResMut<Leaderboard>,
+    q_button: Query<(&Interaction, &LeaderboardButton), Changed<Interaction>>,
+    mut q_text: Query<&mut Text, With<LeaderboardText>>,
+) {
+    for (interaction, button) in q_button.iter() {
+        if *interaction != Interaction::Clicked {
+            continue;
+        }
+
+        let status = match button {
+            LeaderboardButton::Export if leaderboard.export() => {
+                "Exported leaderboard-export.json".to_string()
+            }
+            LeaderboardButton::Export => "Export failed".to_string(),
+            LeaderboardButton::Import => match leaderboard.import(&profile.id) {
+                Some(count) => {
+                    leaderboard.save();
+                    format!("Imported {count} new results")
+                }
+                None => "Nothing to import".to_string(),
+            },
+        };
+
+        for mut text in q_text.iter_mut() {
+            text.sections[0].value = format!(
+                "{}\n{status}",
+                leaderboard.summary(config.daily.unwrap_or_default())
+            );
+        }
+    }
+}
+
 fn click_play_button(
     mut commands: Commands,
+    profile: Option<Res<Profile>>,
+    settings: Res<Settings>,
+    current_state: Res<State<GameState>>,
     mut ev_pick: EventReader<PickingEvent>,
     mut state: ResMut<NextState<GameState>>,
     q_play_btn: Query<With<PlayButton>>,
     mut q_camera: Query<&mut Transform, With<Camera>>,
     q_selection: Query<&MenuSelection, With<ActiveSelection>>,
 ) {
-    const CAMERA_BOARD_OFFSET: Vec3 = Vec3::new(0.0, 9.0, 15.0);
+    if current_state.0.is_overlay() {
+        ev_pick.clear();
+        return;
+    }
 
     for ev in ev_pick.iter() {
         if let PickingEvent::Clicked(e) = ev {
             if q_play_btn.get(*e).is_ok() {
-                let config = q_selection.single().game_config();
+                let config = q_selection
+                    .single()
+                    .game_config()
+                    .with_deck_out(settings.deck_out)
+                    .with_max_turns(settings.max_turns)
+                    .with_seed(rand::random());
 
-                commands.insert_resource(PlayerState::default().with_deck_size(config.deck_size));
-                commands.insert_resource(
-                    OpponentState::default()
-                        .with_deck_size(config.deck_size)
-                        .with_health(config.opponent_hp as i32),
-                );
+                new_game(&mut commands, config, profile.as_deref());
 
                 let mut transform = q_camera.single_mut();
 
-                *transform = Transform::from_translation(CAMERA_BOARD_OFFSET)
+                *transform = Transform::from_translation(settings.camera_offset())
                     .looking_at(Vec3::ZERO, Vec3::Y);
 
                 state.set(GameState::StartGame);
@@ -684,9 +1571,227 @@ fn click_play_button(
     }
 }
 
+fn click_puzzle_option(
+    mut commands: Commands,
+    settings: Res<Settings>,
+    mut state: ResMut<NextState<GameState>>,
+    mut ev_action: EventReader<InputAction>,
+    q_option: Query<(&Interaction, &PuzzleOption), Changed<Interaction>>,
+    mut q_camera: Query<&mut Transform, With<Camera>>,
+) {
+    if ev_action
+        .iter()
+        .any(|action| *action == InputAction::Cancel)
+    {
+        state.set(GameState::Setup);
+        return;
+    }
+
+    for (interaction, option) in q_option.iter() {
+        if *interaction != Interaction::Clicked {
+            continue;
+        }
+
+        match option {
+            PuzzleOption::Back => state.set(GameState::Setup),
+            PuzzleOption::Play(index) => {
+                if let Some(puzzle) = Puzzle::bundled().into_iter().nth(*index) {
+                    start_puzzle(&mut commands, puzzle);
+
+                    let mut transform = q_camera.single_mut();
+
+                    *transform = Transform::from_translation(settings.camera_offset())
+                        .looking_at(Vec3::ZERO, Vec3::Y);
+
+                    state.set(GameState::StartGame);
+                }
+            }
+        }
+    }
+}
+
+fn click_settings_option(
+    mut editing_name: ResMut<EditingName>,
+    mut rebinding: ResMut<Rebinding>,
+    mut settings: ResMut<Settings>,
+    mut state: ResMut<NextState<GameState>>,
+    mut ev_action: EventReader<InputAction>,
+    q_option: Query<(&Interaction, &SettingsOption), Changed<Interaction>>,
+) {
+    let cancelled = ev_action
+        .iter()
+        .any(|action| *action == InputAction::Cancel);
+
+    if cancelled && rebinding.0.is_none() && !editing_name.0 {
+        settings.save();
+        state.set(GameState::Setup);
+
+        return;
+    }
+
+    for (interaction, option) in q_option.iter() {
+        if *interaction != Interaction::Clicked {
+            continue;
+        }
+
+        match option {
+            SettingsOption::Back => {
+                settings.save();
+                state.set(GameState::Setup);
+            }
+            SettingsOption::Binding(action) => {
+                rebinding.0 = Some(*action);
+            }
+            SettingsOption::PlayerName => {
+                settings.player_name.clear();
+                editing_name.0 = true;
+            }
+            _ => settings.cycle(*option),
+        }
+    }
+}
+
+fn click_tutorial_button(
+    mut commands: Commands,
+    settings: Res<Settings>,
+    current_state: Res<State<GameState>>,
+    player: Option<Res<PlayerState>>,
+    mut state: ResMut<NextState<GameState>>,
+    q_button: Query<&Interaction, With<TutorialButton>>,
+    mut q_camera: Query<&mut Transform, With<Camera>>,
+) {
+    let in_menu = menu_available(current_state.0, player.as_deref());
+
+    for interaction in q_button.iter() {
+        if in_menu && *interaction == Interaction::Clicked {
+            start_tutorial(&mut commands);
+
+            let mut transform = q_camera.single_mut();
+
+            *transform = Transform::from_translation(settings.camera_offset())
+                .looking_at(Vec3::ZERO, Vec3::Y);
+
+            state.set(GameState::StartGame);
+        }
+    }
+}
+
+fn close_profile(
+    mut state: ResMut<NextState<GameState>>,
+    mut ev_action: EventReader<InputAction>,
+    q_back: Query<&Interaction, (Changed<Interaction>, With<ProfileBack>)>,
+) {
+    let cancelled = ev_action
+        .iter()
+        .any(|action| *action == InputAction::Cancel);
+
+    if cancelled
+        || q_back
+            .iter()
+            .any(|interaction| *interaction == Interaction::Clicked)
+    {
+        state.set(GameState::Setup);
+    }
+}
+
+fn confirm_mulligan(
+    mut commands: Commands,
+    mut mulligan: ResMut<MulliganState>,
+    mut player_state: ResMut<PlayerState>,
+    tutorial: Option<Res<Tutorial>>,
+    mut ev_pick: EventReader<PickingEvent>,
+    q_dial: Query<With<TurnDial>>,
+    q_deck: Query<&Deck>,
+    mut q_hand: Query<(
+        Entity,
+        &mut Hand,
+        &CardType,
+        &mut Transform,
+        Option<&Mulligan>,
+    )>,
+) {
+    for ev in ev_pick.iter() {
+        if let PickingEvent::Clicked(e) = ev {
+            if mulligan.confirmed
+                || q_dial.get(*e).is_err()
+                || !tutorial
+                    .as_deref()
+                    .map_or(true, |tutorial| tutorial.allows(*e))
+            {
+                continue;
+            }
+
+            mulligan.confirmed = true;
+
+            let mut deck_index = q_deck.iter().map(|deck| deck.0 + 1).max().unwrap_or(0);
+            let mut hand_index = 0;
+            let mut cards = q_hand.iter_mut().collect::<Vec<_>>();
+
+            cards.sort_by(|(_, hand_a, ..), (_, hand_b, ..)| hand_a.0.cmp(&hand_b.0));
+
+            for (entity, mut hand, card_type, mut transform, marked) in cards {
+                if marked.is_some() {
+                    let y = deck_index as f32 * CARD_THICKNESS + CARD_HALF_THICKNESS;
+
+                    player_state.return_to_deck(*card_type);
+                    *transform =
+                        Transform::from_translation(DECK_TRANSLATION + Vec3::new(0.0, y, 0.0))
+                            .with_rotation(Quat::from_rotation_z(180.0_f32.to_radians()));
+                    commands.entity(entity).despawn_descendants();
+                    commands
+                        .entity(entity)
+                        .remove::<(CardType, Hand, Mulligan)>()
+                        .insert((Deck(deck_index), Draw));
+
+                    deck_index += 1;
+                } else {
+                    hand.0 = hand_index;
+                    transform.translation.x = hand_index as f32 * CARD_WIDTH - 5.0;
+                    hand_index += 1;
+                }
+            }
+        }
+    }
+}
+
+fn deal_opening_hand(
+    mut commands: Commands,
+    player_state: Res<PlayerState>,
+    q_deck: Query<(Entity, &Deck), Without<Hand>>,
+) {
+    let draw_count = OPENING_HAND_SIZE.min(player_state.deck_size());
+
+    mark_top_of_deck(&mut commands, &q_deck, draw_count);
+}
+
+fn despawn_loading_screen(mut commands: Commands, q_screen: Query<Entity, With<LoadingScreen>>) {
+    for entity in q_screen.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+fn despawn_profile_screen(mut commands: Commands, q_screen: Query<Entity, With<ProfileScreen>>) {
+    for entity in q_screen.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+fn despawn_puzzle_screen(mut commands: Commands, q_screen: Query<Entity, With<PuzzleScreen>>) {
+    for entity in q_screen.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+fn despawn_settings_menu(mut commands: Commands, q_menu: Query<Entity, With<SettingsMenu>>) {
+    for entity in q_menu.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
 fn draw_cards(
     mut commands: Commands,
     card_assets: Res<CardAssets>,
+    settings: Res<Settings>,
     mut player_state: ResMut<PlayerState>,
     mut q_draw: Query<(Entity, &mut Transform), (With<Draw>, With<Deck>, Without<Hand>)>,
     q_hand: Query<With<Hand>>,
@@ -699,14 +1804,16 @@ fn draw_cards(
             *transform = transform.with_rotation(Quat::from_rotation_z(0.0));
 
             let end = Vec3::new(x, CARD_HALF_THICKNESS, HAND_Z);
-            let tween = Tween::new(
-                EaseFunction::QuadraticInOut,
-                Duration::from_millis(500),
-                TransformPositionLens {
-                    start: transform.translation,
-                    end,
-                },
-            );
+            let animation = settings.animation_duration(500).map(|duration| {
+                Tween::new(
+                    EaseFunction::QuadraticInOut,
+                    duration,
+                    TransformPositionLens {
+                        start: transform.translation,
+                        end,
+                    },
+                )
+            });
 
             x += CARD_WIDTH;
 
@@ -731,84 +1838,330 @@ fn draw_cards(
                     Attack(attributes.attack as i32),
                     Cost(attributes.cost as i32),
                     Health(attributes.health as i32),
-                    Animator::new(tween),
                 ))
                 .insert(PickableBundle::default())
                 .push_children(&[child]);
 
+            if let Some(tween) = animation {
+                commands.entity(entity).insert(Animator::new(tween));
+            } else {
+                transform.translation = end;
+            }
+
             hand_size += 1;
         }
     }
 }
 
-fn draw_cards_opponent(mut state: ResMut<OpponentState>) {
+fn draw_cards_opponent(config: Res<GameConfig>, mut state: ResMut<OpponentState>) {
+    if config.deck_out == DeckOutRule::Reshuffle && state.deck_size() == 0 {
+        state.reshuffle_graveyard();
+    }
+
     state.draw_cards();
 }
 
-fn game_over(
-    mut player: ResMut<PlayerState>,
-    q_acting: Query<(With<PerformingAction>, Without<Camera>, Without<Menu>)>,
-    q_menu: Query<&Transform, (With<Menu>, Without<Camera>)>,
-    mut q_camera: Query<&mut Transform, With<Camera>>,
+fn expire_modifiers<C: Component, S: PlayableState>(
+    mut commands: Commands,
+    player_state: Res<S>,
+    mut q_cards: Query<
+        (
+            Entity,
+            &CardType,
+            &mut StatModifiers,
+            &mut Attack,
+            &mut Health,
+        ),
+        With<C>,
+    >,
 ) {
-    if q_acting.iter().next().is_none() && !player.sent_to_menu {
-        player.sent_to_menu = true;
+    let turn = player_state.get_turn();
 
-        let menu = q_menu.single();
-        let mut transform = q_camera.single_mut();
+    for (entity, card_type, mut modifiers, mut attack, mut health) in q_cards.iter_mut() {
+        modifiers.expire(turn);
+        modifiers.sync(*card_type, &mut attack, &mut health);
 
-        *transform = Transform::from_translation(menu.translation + CAMERA_MENU_OFFSET)
-            .looking_at(menu.translation, Vec3::Y);
+        if health.0 <= 0 {
+            commands.entity(entity).insert(Killed);
+        }
     }
 }
 
-fn hover_button(
-    materials: Res<MenuMaterials>,
-    mut ev_pick: EventReader<PickingEvent>,
-    mut q_button: Query<
-        (&mut Handle<StandardMaterial>, Option<&ActiveSelection>),
-        (With<Button>, Without<ActiveSelection>),
-    >,
+fn finish_mulligan(
+    mulligan: Res<MulliganState>,
+    mut state: ResMut<NextState<GameState>>,
+    q_draw: Query<With<Draw>>,
 ) {
-    for ev in ev_pick.iter() {
-        match ev {
-            PickingEvent::Hover(HoverEvent::JustEntered(e)) => {
-                if let Ok((mut material, _)) = q_button.get_mut(*e) {
-                    *material = materials.button_material_hovered.clone();
-                }
-            }
-            PickingEvent::Hover(HoverEvent::JustLeft(e)) => {
-                if let Ok((mut material, active)) = q_button.get_mut(*e) {
-                    if active.is_some() {
-                        *material = materials.button_material_active.clone();
-                    } else {
-                        *material = materials.button_material.clone();
-                    }
-                }
-            }
-            _ => {}
-        }
+    if mulligan.confirmed && q_draw.iter().next().is_none() {
+        state.set(GameState::Mulligan.next().unwrap());
     }
 }
 
-fn hover_card_placeholder(
-    materials: Res<CardPlaceholderMaterials>,
-    board: Res<PlayerBoard>,
-    mut ev_pick: EventReader<PickingEvent>,
-    mut q_placeholder: Query<(&CardPlaceholder, &mut Handle<StandardMaterial>)>,
+fn focus_input(
+    current_state: Res<State<GameState>>,
+    mut focus: ResMut<Focus>,
+    mut ev_action: EventReader<InputAction>,
+    mut ev_pick: EventWriter<PickingEvent>,
+    q_hand: Query<(Entity, &Hand)>,
+    q_placeholder: Query<(Entity, &CardPlaceholder), With<Player>>,
     q_picked: Query<With<Picked>>,
+    q_dial: Query<Entity, With<TurnDial>>,
+    q_selection: Query<(Entity, &MenuSelection, Option<&ActiveSelection>)>,
+    q_play_btn: Query<Entity, With<PlayButton>>,
 ) {
-    for ev in ev_pick.iter() {
-        if q_picked.iter().next().is_none() {
-            return;
+    let mut hand = q_hand.iter().collect::<Vec<_>>();
+    let mut placeholders = q_placeholder.iter().collect::<Vec<_>>();
+    let mut selections = q_selection.iter().collect::<Vec<_>>();
+
+    hand.sort_by_key(|(_, card)| card.0);
+    placeholders.sort_by_key(|(_, placeholder)| placeholder.0);
+    selections.sort_by_key(|(_, selection, _)| **selection as u8);
+
+    let active_selection = selections
+        .iter()
+        .find(|(_, _, active)| active.is_some())
+        .map(|(entity, _, _)| *entity);
+    let hand = hand
+        .into_iter()
+        .map(|(entity, _)| entity)
+        .collect::<Vec<_>>();
+    let placeholders = placeholders
+        .into_iter()
+        .map(|(entity, _)| entity)
+        .collect::<Vec<_>>();
+    let selections = selections
+        .into_iter()
+        .map(|(entity, _, _)| entity)
+        .collect::<Vec<_>>();
+    let in_menu = matches!(
+        current_state.0,
+        GameState::Setup | GameState::Win | GameState::Lose | GameState::Draw
+    );
+    let in_hand = matches!(current_state.0, GameState::Mulligan | GameState::PlayerTurn);
+
+    for action in ev_action.iter() {
+        if *action == InputAction::Cancel {
+            if let Some(current) = focus.0.take() {
+                ev_pick.send(PickingEvent::Hover(HoverEvent::JustLeft(current)));
+            }
+
+            continue;
         }
 
-        match ev {
-            PickingEvent::Hover(HoverEvent::JustEntered(e)) => {
-                if let Ok((placeholder, mut material)) = q_placeholder.get_mut(*e) {
-                    if board.unoccupied(placeholder.0) {
-                        *material = materials.hovered.clone();
-                    }
+        let current = focus.0;
+        let forward = matches!(action, InputAction::NextHand | InputAction::Right);
+
+        let (target, click) = match action {
+            InputAction::Confirm if in_menu => (q_play_btn.get_single().ok(), true),
+            InputAction::Confirm if current_state.0 == GameState::Mulligan => {
+                (q_dial.get_single().ok(), true)
+            }
+            InputAction::Confirm if current_state.0 == GameState::PlayerTurn => {
+                if q_picked.is_empty() {
+                    (q_dial.get_single().ok(), true)
+                } else {
+                    (current.filter(|entity| placeholders.contains(entity)), true)
+                }
+            }
+            InputAction::Select => (current, true),
+            InputAction::Hand(index) if in_hand => (hand.get(*index as usize).copied(), true),
+            InputAction::Left | InputAction::Right if in_menu => {
+                let current = current
+                    .filter(|entity| selections.contains(entity))
+                    .or(active_selection);
+
+                (step_focus(&selections, current, forward), true)
+            }
+            InputAction::Left | InputAction::Right if current_state.0 == GameState::PlayerTurn => {
+                (step_focus(&placeholders, current, forward), false)
+            }
+            InputAction::Left
+            | InputAction::NextHand
+            | InputAction::PreviousHand
+            | InputAction::Right
+                if in_hand =>
+            {
+                (step_focus(&hand, current, forward), false)
+            }
+            _ => continue,
+        };
+
+        let target = if let Some(target) = target {
+            target
+        } else {
+            continue;
+        };
+
+        if current != Some(target) {
+            if let Some(current) = current {
+                ev_pick.send(PickingEvent::Hover(HoverEvent::JustLeft(current)));
+            }
+
+            ev_pick.send(PickingEvent::Hover(HoverEvent::JustEntered(target)));
+            focus.0 = Some(target);
+        }
+
+        if click {
+            ev_pick.send(PickingEvent::Clicked(target));
+        }
+    }
+}
+
+fn click_results_button(
+    mut commands: Commands,
+    config: Res<GameConfig>,
+    profile: Option<Res<Profile>>,
+    puzzle: Option<Res<ActivePuzzle>>,
+    settings: Res<Settings>,
+    tutorial: Option<Res<Tutorial>>,
+    mut state: ResMut<NextState<GameState>>,
+    q_button: Query<(&Interaction, &ResultsButton), Changed<Interaction>>,
+    q_results: Query<Entity, With<ResultsScreen>>,
+    mut q_camera: Query<&mut Transform, With<Camera>>,
+) {
+    for (interaction, button) in q_button.iter() {
+        if *interaction != Interaction::Clicked {
+            continue;
+        }
+
+        for entity in q_results.iter() {
+            commands.entity(entity).despawn_recursive();
+        }
+
+        if let ResultsButton::Rematch = button {
+            if let Some(puzzle) = &puzzle {
+                start_puzzle(&mut commands, puzzle.0.clone());
+            } else if tutorial.is_some() {
+                start_tutorial(&mut commands);
+            } else {
+                new_game(&mut commands, *config, profile.as_deref());
+            }
+
+            let mut transform = q_camera.single_mut();
+
+            *transform = Transform::from_translation(settings.camera_offset())
+                .looking_at(Vec3::ZERO, Vec3::Y);
+
+            state.set(GameState::StartGame);
+        }
+    }
+}
+
+fn game_over(
+    mut commands: Commands,
+    config: Res<GameConfig>,
+    leaderboard: Option<Res<Leaderboard>>,
+    stats: Res<MatchStats>,
+    ui_assets: Res<UiAssets>,
+    mut player: ResMut<PlayerState>,
+    q_acting: Query<(With<PerformingAction>, Without<Camera>, Without<Menu>)>,
+    q_menu: Query<&Transform, (With<Menu>, Without<Camera>)>,
+    mut q_camera: Query<&mut Transform, With<Camera>>,
+) {
+    if q_acting.iter().next().is_none() && !player.sent_to_menu {
+        player.sent_to_menu = true;
+
+        spawn_results(
+            &mut commands,
+            &ui_assets,
+            stats.summary(player.turn, config.seed),
+            config
+                .daily
+                .zip(leaderboard)
+                .map(|(day, leaderboard)| leaderboard.summary(day)),
+        );
+
+        let menu = q_menu.single();
+        let mut transform = q_camera.single_mut();
+
+        *transform = Transform::from_translation(menu.translation + CAMERA_MENU_OFFSET)
+            .looking_at(menu.translation, Vec3::Y);
+    }
+}
+
+fn hide_game_over_text(mut q_text: Query<&mut Visibility, With<GameOverText>>) {
+    for mut visibility in q_text.iter_mut() {
+        *visibility = Visibility::Hidden;
+    }
+}
+
+fn hover_button(
+    materials: Res<MenuMaterials>,
+    current_state: Res<State<GameState>>,
+    mut ev_pick: EventReader<PickingEvent>,
+    mut q_button: Query<
+        (&mut Handle<StandardMaterial>, Option<&ActiveSelection>),
+        (With<Button>, Without<ActiveSelection>),
+    >,
+) {
+    if current_state.0.is_overlay() {
+        ev_pick.clear();
+        return;
+    }
+
+    for ev in ev_pick.iter() {
+        match ev {
+            PickingEvent::Hover(HoverEvent::JustEntered(e)) => {
+                if let Ok((mut material, _)) = q_button.get_mut(*e) {
+                    *material = materials.button_material_hovered.clone();
+                }
+            }
+            PickingEvent::Hover(HoverEvent::JustLeft(e)) => {
+                if let Ok((mut material, active)) = q_button.get_mut(*e) {
+                    if active.is_some() {
+                        *material = materials.button_material_active.clone();
+                    } else {
+                        *material = materials.button_material.clone();
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+fn hover_card(
+    mut ev_pick: EventReader<PickingEvent>,
+    q_cards: Query<With<CardType>>,
+    mut q_tooltip: Query<&mut Tooltip>,
+) {
+    for ev in ev_pick.iter() {
+        for mut tooltip in q_tooltip.iter_mut() {
+            match ev {
+                PickingEvent::Hover(HoverEvent::JustEntered(e)) => {
+                    if q_cards.get(*e).is_ok() {
+                        tooltip.0 = Some(*e);
+                    }
+                }
+                PickingEvent::Hover(HoverEvent::JustLeft(e)) => {
+                    if tooltip.0 == Some(*e) {
+                        tooltip.0 = None;
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+fn hover_card_placeholder(
+    materials: Res<CardPlaceholderMaterials>,
+    board: Res<PlayerBoard>,
+    mut ev_pick: EventReader<PickingEvent>,
+    mut q_placeholder: Query<(&CardPlaceholder, &mut Handle<StandardMaterial>)>,
+    q_picked: Query<With<Picked>>,
+) {
+    for ev in ev_pick.iter() {
+        if q_picked.iter().next().is_none() {
+            return;
+        }
+
+        match ev {
+            PickingEvent::Hover(HoverEvent::JustEntered(e)) => {
+                if let Ok((placeholder, mut material)) = q_placeholder.get_mut(*e) {
+                    if board.unoccupied(placeholder.0) {
+                        *material = materials.hovered.clone();
+                    }
                 }
             }
             PickingEvent::Hover(HoverEvent::JustLeft(e)) => {
@@ -867,13 +2220,47 @@ fn hover_hand(
     }
 }
 
+fn edit_player_name(
+    keys: Res<Input<KeyCode>>,
+    mut editing_name: ResMut<EditingName>,
+    mut settings: ResMut<Settings>,
+    mut ev_character: EventReader<ReceivedCharacter>,
+) {
+    if !editing_name.0 {
+        ev_character.clear();
+
+        return;
+    }
+
+    for ev in ev_character.iter() {
+        settings.type_name(ev.char);
+    }
+
+    if keys.just_pressed(KeyCode::Back) {
+        settings.erase_name();
+    }
+
+    if keys.any_just_pressed([KeyCode::Escape, KeyCode::Return]) {
+        settings.finish_name();
+        editing_name.0 = false;
+    }
+}
+
 fn end_turn(
+    tutorial: Option<Res<Tutorial>>,
     mut ev_pick: EventReader<PickingEvent>,
     mut state: ResMut<NextState<GameState>>,
     mut q_dial: Query<&mut Transform, With<TurnDial>>,
 ) {
     for ev in ev_pick.iter() {
         if let PickingEvent::Clicked(e) = ev {
+            if !tutorial
+                .as_deref()
+                .map_or(true, |tutorial| tutorial.allows(*e))
+            {
+                continue;
+            }
+
             if let Ok(mut transform) = q_dial.get_mut(*e) {
                 *transform = transform.with_rotation(Quat::from_rotation_y(180.0_f32.to_radians()));
                 state.set(GameState::PlayerAttacking);
@@ -898,38 +2285,144 @@ fn mark_cards_to_draw(
     q_deck: Query<(Entity, &Deck), Without<Hand>>,
     q_hand: Query<With<Hand>>,
 ) {
-    let mut hand_size = q_hand.iter().count() as u32;
+    let hand_size = q_hand.iter().count() as u32;
+    let draw_count = player_state.draw_count();
+
+    player_state.turn += 1;
 
     if hand_size >= player_state.max_hand_size {
         return;
     }
 
-    let draw_count = player_state.draw_count();
-    player_state.turn += 1;
+    let draw_count = draw_count.min(player_state.max_hand_size - hand_size);
+
+    mark_top_of_deck(&mut commands, &q_deck, draw_count);
+}
+
+fn mark_top_of_deck(
+    commands: &mut Commands,
+    q_deck: &Query<(Entity, &Deck), Without<Hand>>,
+    count: u32,
+) {
     let mut sorted_deck = q_deck.iter().collect::<Vec<_>>();
 
     sorted_deck.sort_by(|(_, deck_a), (_, deck_b)| deck_a.0.partial_cmp(&deck_b.0).unwrap());
 
-    for _ in 0..draw_count {
-        if hand_size < player_state.max_hand_size {
-            if let Some((entity, __)) = sorted_deck.pop() {
-                commands.entity(entity).insert(Draw);
-            }
+    for _ in 0..count {
+        if let Some((entity, _)) = sorted_deck.pop() {
+            commands.entity(entity).insert(Draw);
+        }
+    }
+}
+
+fn menu_available(current_state: GameState, player: Option<&PlayerState>) -> bool {
+    // After a game ends, wait until the camera is back at the menu.
+    match current_state {
+        GameState::Setup => true,
+        GameState::Win | GameState::Lose | GameState::Draw => {
+            player.map_or(false, |player| player.sent_to_menu)
+        }
+        _ => false,
+    }
+}
+
+fn mulligan_opponent(mut opponent_state: ResMut<OpponentState>) {
+    opponent_state.deal_opening_hand();
+}
+
+fn new_game(commands: &mut Commands, config: GameConfig, profile: Option<&Profile>) {
+    let pool = match profile {
+        Some(profile) if config.daily.is_none() => profile.card_pool(),
+        _ => CardType::all().to_vec(),
+    };
+
+    commands.insert_resource(PlayerState::default().with_deck_size(
+        config.deck_size,
+        config.seed,
+        &pool,
+    ));
+    commands.insert_resource(
+        OpponentState::default()
+            .with_deck_size(
+                config.deck_size,
+                config.seed.wrapping_add(1),
+                &CardType::all(),
+            )
+            .with_health(config.opponent_hp as i32),
+    );
+    commands.insert_resource(config);
+    commands.remove_resource::<ActivePuzzle>();
+    commands.remove_resource::<Tutorial>();
+}
+
+fn open_settings(
+    current_state: Res<State<GameState>>,
+    player: Option<Res<PlayerState>>,
+    mut state: ResMut<NextState<GameState>>,
+    mut ev_action: EventReader<InputAction>,
+    q_button: Query<&Interaction, With<SettingsButton>>,
+) {
+    let in_menu = menu_available(current_state.0, player.as_deref());
+    let mut open = ev_action
+        .iter()
+        .any(|action| *action == InputAction::Settings);
+
+    for interaction in q_button.iter() {
+        open |= *interaction == Interaction::Clicked;
+    }
+
+    if in_menu && open {
+        state.set(GameState::Settings);
+    }
+}
+
+fn open_profile(
+    current_state: Res<State<GameState>>,
+    player: Option<Res<PlayerState>>,
+    mut state: ResMut<NextState<GameState>>,
+    q_button: Query<&Interaction, With<ProfileButton>>,
+) {
+    let in_menu = menu_available(current_state.0, player.as_deref());
+
+    for interaction in q_button.iter() {
+        if in_menu && *interaction == Interaction::Clicked {
+            state.set(GameState::Profile);
         }
+    }
+}
+
+fn open_puzzles(
+    current_state: Res<State<GameState>>,
+    player: Option<Res<PlayerState>>,
+    mut state: ResMut<NextState<GameState>>,
+    q_button: Query<&Interaction, With<PuzzleButton>>,
+) {
+    let in_menu = menu_available(current_state.0, player.as_deref());
 
-        hand_size += 1;
+    for interaction in q_button.iter() {
+        if in_menu && *interaction == Interaction::Clicked {
+            state.set(GameState::Puzzles);
+        }
     }
 }
 
 fn pick_from_hand(
     mut commands: Commands,
     player_state: Res<PlayerState>,
+    tutorial: Option<Res<Tutorial>>,
     mut ev_pick: EventReader<PickingEvent>,
     mut q_hand: Query<(&Cost, &mut Transform), (With<Hand>, Without<Picked>)>,
     mut q_picked: Query<(Entity, &mut Transform), With<Picked>>,
 ) {
     for ev in ev_pick.iter() {
         if let PickingEvent::Clicked(e) = ev {
+            if !tutorial
+                .as_deref()
+                .map_or(true, |tutorial| tutorial.allows(*e))
+            {
+                continue;
+            }
+
             if let Ok((cost, mut transform)) = q_hand.get_mut(*e) {
                 if cost.0 <= player_state.available_power {
                     for (entity, mut picked_transform) in q_picked.iter_mut() {
@@ -946,10 +2439,42 @@ fn pick_from_hand(
     }
 }
 
+fn pick_mulligan(
+    mut commands: Commands,
+    mulligan: Res<MulliganState>,
+    tutorial: Option<Res<Tutorial>>,
+    mut ev_pick: EventReader<PickingEvent>,
+    mut q_hand: Query<(&mut Transform, Option<&Mulligan>), With<Hand>>,
+) {
+    for ev in ev_pick.iter() {
+        if let PickingEvent::Clicked(e) = ev {
+            if mulligan.confirmed
+                || !tutorial
+                    .as_deref()
+                    .map_or(true, |tutorial| tutorial.allows(*e))
+            {
+                continue;
+            }
+
+            if let Ok((mut transform, marked)) = q_hand.get_mut(*e) {
+                if marked.is_some() {
+                    transform.translation.z += 1.0;
+                    commands.entity(*e).remove::<Mulligan>();
+                } else {
+                    transform.translation.z -= 1.0;
+                    commands.entity(*e).insert(Mulligan);
+                }
+            }
+        }
+    }
+}
+
 fn play_card(
     mut commands: Commands,
     placeholder_materials: Res<CardPlaceholderMaterials>,
     mut board: ResMut<PlayerBoard>,
+    tutorial: Option<Res<Tutorial>>,
+    mut undo: ResMut<UndoStack>,
     mut ev_pick: EventReader<PickingEvent>,
     mut ev_played: EventWriter<CardPlayedEvent>,
     mut q_placeholder: Query<(&CardPlaceholder, &Transform, &mut Handle<StandardMaterial>)>,
@@ -961,6 +2486,13 @@ fn play_card(
     for ev in ev_pick.iter() {
         if let Ok((picked_entity, hand, card_type, mut transform)) = q_picked.get_single_mut() {
             if let PickingEvent::Clicked(e) = ev {
+                if !tutorial
+                    .as_deref()
+                    .map_or(true, |tutorial| tutorial.allows(*e))
+                {
+                    continue;
+                }
+
                 if let Ok((placeholder, placeholder_transform, mut material)) =
                     q_placeholder.get_mut(*e)
                 {
@@ -970,6 +2502,10 @@ fn play_card(
                         *material = placeholder_materials.invisable.clone();
                         transform.translation = placeholder_transform.translation;
                         board.place(index, picked_entity, *card_type);
+                        undo.placements.push(PlayedCard {
+                            entity: picked_entity,
+                            hand: hand.0,
+                        });
 
                         ev_played.send(CardPlayedEvent {
                             entity: picked_entity,
@@ -979,7 +2515,7 @@ fn play_card(
                             .entity(picked_entity)
                             .remove::<Picked>()
                             .remove::<Hand>()
-                            .insert(PendingAbility);
+                            .insert((PendingAbility, StatModifiers::default()));
                     }
                 }
             }
@@ -990,9 +2526,11 @@ fn play_card(
 fn play_opponent_cards(
     mut commands: Commands,
     card_assets: Res<CardAssets>,
+    settings: Res<Settings>,
     mut state: ResMut<NextState<GameState>>,
     mut board: ResMut<OpponentBoard>,
     mut opponent_state: ResMut<OpponentState>,
+    mut tutorial: Option<ResMut<Tutorial>>,
     mut ev_played: EventWriter<CardPlayedEvent>,
     q_placeholder: Query<(&CardPlaceholder, &Transform), With<Opponent>>,
     q_killed: Query<(With<Killed>, With<Opponent>, Without<CardPlaceholder>)>,
@@ -1007,96 +2545,286 @@ fn play_opponent_cards(
         return;
     }
 
-    if board.has_empty_place() && opponent_state.can_play_card() {
+    let turn = opponent_state.turn;
+    let play = if let Some(moves) = tutorial
+        .as_deref_mut()
+        .and_then(|tutorial| tutorial.opponent_moves(turn))
+    {
+        // Scripted turns only play their listed moves.
+        let mut play = None;
+
+        while let Some(OpponentMove { card_type, lane }) = moves.pop_front() {
+            if lane < 4 && board.unoccupied(lane) {
+                if let Some(card) = opponent_state.play_card_of(card_type) {
+                    play = Some((card, lane));
+                    break;
+                }
+            }
+
+            warn!("Skipping scripted {card_type:?} in lane {lane} on turn {turn}");
+        }
+
+        play
+    } else if board.has_empty_place() && opponent_state.can_play_card() {
         let card = opponent_state.play_card().unwrap();
-        let index = board.random_empty_place().unwrap();
+        let index = board.random_empty_place(opponent_state.rng()).unwrap();
+
+        Some((card, index))
+    } else {
+        None
+    };
+
+    if let Some((card, index)) = play {
         let (_, transform) = q_placeholder.iter().find(|(p, _)| p.0 == index).unwrap();
-        let end = transform.translation;
-        let start = end + Vec3::new(0.0, 0.0, -10.0);
-        let transform = transform.with_translation(start);
-        let tween = Tween::new(
-            EaseFunction::QuadraticInOut,
-            Duration::from_millis(600),
-            TransformPositionLens { start, end },
-        )
-        .with_completed_event(TWEEN_EVENT_REMOVE_PERFORM_ACTION);
-        let attributes = card.attributes();
-        let mesh = card.mesh(&card_assets);
-        let material = card.material(&card_assets);
-        let entity = commands
-            .spawn((
-                PbrBundle {
-                    mesh: card_assets.card_mesh.clone(),
-                    material: card_assets.card_material.clone(),
-                    transform,
-                    ..default()
-                },
-                card,
-                Attack(attributes.attack as i32),
-                Health(attributes.health as i32),
-                Opponent,
-                PendingAbility,
-                Animator::new(tween),
-                PerformingAction,
-            ))
-            .with_children(|parent| {
-                parent.spawn(PbrBundle {
-                    mesh: mesh.clone(),
-                    material: material.clone(),
-                    ..default()
-                });
-            })
-            .id();
+        let start = transform.translation + Vec3::new(0.0, 0.0, -10.0);
+        let entity = spawn_board_card::<Opponent>(
+            &mut commands,
+            &card_assets,
+            &settings,
+            card,
+            start,
+            *transform,
+        );
 
+        commands.entity(entity).insert(PendingAbility);
         board.place(index, entity, card);
         ev_played.send(CardPlayedEvent { entity, index });
-        opponent_state.available_power -= attributes.cost as i32;
+        opponent_state.available_power -= card.attributes().cost as i32;
+
+        if let CardAbility::Resurrect = card.ability() {
+            if let Some(lane) = board.state().empty_place_near(entity) {
+                if let Some(revived) = opponent_state.resurrect() {
+                    let (_, transform) = q_placeholder.iter().find(|(p, _)| p.0 == lane).unwrap();
+                    let entity = spawn_board_card::<Opponent>(
+                        &mut commands,
+                        &card_assets,
+                        &settings,
+                        revived,
+                        OpponentState::discard_pile_translation(),
+                        *transform,
+                    );
+
+                    commands.entity(entity).insert(PendingAbility);
+                    board.place(lane, entity, revived);
+                    ev_played.send(CardPlayedEvent {
+                        entity,
+                        index: lane,
+                    });
+                }
+            } else {
+                opponent_state.resurrect_to_hand();
+            }
+        }
     } else if q_acting.iter().next().is_none() {
         state.set(GameState::OpponentPlayCards.next().unwrap());
     }
 }
 
-fn receive_ability<C: Component, B: Board>(
+fn preview_placement(
     mut commands: Commands,
-    board: Res<B>,
-    mut q_pending: Query<(Entity, &mut Attack, &mut Health), (With<C>, With<PendingAbility>)>,
-    q_cards: Query<(Entity, &CardType), (With<C>, Without<Hand>, Without<PendingAbility>)>,
+    board: Res<PlayerBoard>,
+    opponent_board: Res<OpponentBoard>,
+    card_assets: Res<CardAssets>,
+    preview_materials: Res<PreviewMaterials>,
+    mut ev_pick: EventReader<PickingEvent>,
+    q_placeholder: Query<&CardPlaceholder, With<Player>>,
+    q_picked: Query<(Entity, &CardType), With<Picked>>,
+    q_cards: Query<&Transform, (With<CardType>, Without<Picked>)>,
+    q_preview: Query<Entity, With<Preview>>,
 ) {
-    for (entity, mut attack, mut health) in q_pending.iter_mut() {
-        for (other_entity, card_type) in q_cards.iter() {
-            if card_type
-                .affects(other_entity, board.state())
-                .contains(&entity)
-            {
-                card_type.effect().apply(&mut attack, &mut health);
-            }
-        }
-
+    for ev in ev_pick.iter() {
+        let (picked_entity, picked_type) = if let Ok(picked) = q_picked.get_single() {
+            picked
+        } else {
+            return;
+        };
+
+        match ev {
+            PickingEvent::Hover(HoverEvent::JustEntered(e)) => {
+                let index = if let Ok(placeholder) = q_placeholder.get(*e) {
+                    placeholder.0
+                } else {
+                    continue;
+                };
+
+                if !board.unoccupied(index) {
+                    continue;
+                }
+
+                let mut state = board.state().clone();
+
+                state.place(index, picked_entity, *picked_type);
+
+                let mut previews = picked_type
+                    .affects(picked_entity, &state)
+                    .into_iter()
+                    .map(|entity| (entity, Preview::Buffed))
+                    .collect::<Vec<_>>();
+
+                for placement in state.others(picked_entity) {
+                    if placement
+                        .card_type
+                        .affects(placement.entity, &state)
+                        .contains(&picked_entity)
+                    {
+                        previews.push((placement.entity, Preview::BuffedBy));
+                    }
+                }
+
+                if let Some(across) = state.across(opponent_board.state(), picked_entity) {
+                    previews.push((across.entity, Preview::Facing));
+                }
+
+                for (entity, preview) in previews {
+                    if let Ok(transform) = q_cards.get(entity) {
+                        let scale = preview.scale();
+
+                        commands.spawn((
+                            PbrBundle {
+                                mesh: card_assets.card_mesh.clone(),
+                                material: preview.material(&preview_materials),
+                                transform: transform
+                                    .with_translation(
+                                        transform.translation - Vec3::Y * CARD_THICKNESS * scale,
+                                    )
+                                    .with_scale(Vec3::new(scale, 1.0, scale)),
+                                ..default()
+                            },
+                            preview,
+                        ));
+                    }
+                }
+            }
+            PickingEvent::Hover(HoverEvent::JustLeft(e)) => {
+                if q_placeholder.get(*e).is_ok() {
+                    for entity in q_preview.iter() {
+                        commands.entity(entity).despawn_recursive();
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+fn read_input(
+    settings: Res<Settings>,
+    keys: Res<Input<KeyCode>>,
+    mouse: Res<Input<MouseButton>>,
+    buttons: Res<Input<GamepadButton>>,
+    mut ev_action: EventWriter<InputAction>,
+) {
+    let ctrl = keys.any_pressed([KeyCode::LControl, KeyCode::RControl]);
+
+    for key in keys.get_just_pressed() {
+        let action = ctrl
+            .then(|| settings.bindings.ctrl_key_action(*key))
+            .flatten()
+            .or_else(|| settings.bindings.key_action(*key));
+
+        if let Some(action) = action {
+            ev_action.send(action);
+        }
+    }
+
+    for button in mouse.get_just_pressed() {
+        if let Some(action) = settings.bindings.mouse_action(*button) {
+            ev_action.send(action);
+        }
+    }
+
+    for button in buttons.get_just_pressed() {
+        if let Some(action) = settings.bindings.button_action(button.button_type) {
+            ev_action.send(action);
+        }
+    }
+}
+
+fn rebind_input(
+    keys: Res<Input<KeyCode>>,
+    buttons: Res<Input<GamepadButton>>,
+    mut rebinding: ResMut<Rebinding>,
+    mut settings: ResMut<Settings>,
+) {
+    let action = if let Some(action) = rebinding.0 {
+        action
+    } else {
+        return;
+    };
+
+    if let Some(key) = keys.get_just_pressed().next() {
+        settings.bindings.rebind_key(action, *key);
+        rebinding.0 = None;
+    } else if let Some(button) = buttons.get_just_pressed().next() {
+        settings.bindings.rebind_button(action, button.button_type);
+        rebinding.0 = None;
+    }
+}
+
+fn receive_ability<C: Component, B: Board>(
+    mut commands: Commands,
+    board: Res<B>,
+    mut q_pending: Query<
+        (
+            Entity,
+            &CardType,
+            &mut StatModifiers,
+            &mut Attack,
+            &mut Health,
+        ),
+        (With<C>, With<PendingAbility>),
+    >,
+    q_cards: Query<(Entity, &CardType), (With<C>, Without<Hand>, Without<PendingAbility>)>,
+) {
+    for (entity, card_type, mut modifiers, mut attack, mut health) in q_pending.iter_mut() {
+        for (other_entity, other_type) in q_cards.iter() {
+            if other_type
+                .affects(other_entity, board.state())
+                .contains(&entity)
+            {
+                modifiers.add(other_entity, other_type.effect());
+            }
+        }
+
+        modifiers.sync(*card_type, &mut attack, &mut health);
+
         commands.entity(entity).remove::<PendingAbility>();
     }
 }
 
-fn remove_killed<C: Component, B: Board>(
+fn remove_killed<C: Component, B: Board, S: PlayableState>(
     mut commands: Commands,
     mut board: ResMut<B>,
+    mut player_state: ResMut<S>,
     q_killed: Query<(Entity, &CardType), (With<C>, With<Killed>)>,
-    mut q_cards: Query<(&mut Attack, &mut Health), (With<C>, Without<Killed>)>,
+    mut q_cards: Query<
+        (
+            Entity,
+            &CardType,
+            &mut StatModifiers,
+            &mut Attack,
+            &mut Health,
+        ),
+        (With<C>, Without<Killed>),
+    >,
 ) {
     if let Some((entity, card_type)) = q_killed.iter().next() {
-        let affects = card_type.affects(entity, board.state());
-        let effect = card_type.effect();
+        for (other_entity, other_type, mut modifiers, mut attack, mut health) in q_cards.iter_mut()
+        {
+            if !modifiers.contains(entity) {
+                continue;
+            }
 
-        for entity in affects {
-            if let Ok((mut attack, mut health)) = q_cards.get_mut(entity) {
-                effect.remove(&mut attack, &mut health);
+            modifiers.remove(entity);
+            modifiers.sync(*other_type, &mut attack, &mut health);
 
-                if health.0 <= 0 {
-                    commands.entity(entity).insert(Killed);
-                }
+            if health.0 <= 0 {
+                commands.entity(other_entity).insert(Killed);
             }
         }
 
         board.remove(entity);
+        player_state.bury(*card_type);
         commands.entity(entity).despawn_recursive();
     }
 }
@@ -1109,6 +2837,98 @@ fn remove_perform_action(mut commands: Commands, mut ev_completed: EventReader<T
     }
 }
 
+fn record_stats(
+    mut stats: ResMut<MatchStats>,
+    mut ev_attacked: EventReader<AttackedEvent>,
+    mut ev_hit: EventReader<CardHitEvent>,
+    mut ev_played: EventReader<CardPlayedEvent>,
+    q_card: Query<(&CardType, Option<&Player>)>,
+    q_damaged: Query<(&Damage, Option<&Player>), Added<Damage>>,
+    q_killed: Query<Option<&Player>, (With<CardType>, Added<Killed>)>,
+) {
+    for ev in ev_attacked.iter() {
+        match ev {
+            AttackedEvent::Opponent(damage, DamageSource::Attack) => stats.damage_dealt += damage,
+            AttackedEvent::Player(damage, DamageSource::Attack) => stats.damage_taken += damage,
+            _ => {}
+        }
+    }
+
+    for ev in ev_hit.iter() {
+        if let Ok((card_type, Some(_))) = q_card.get(ev.attacker) {
+            stats.record_hit(ev.attacker, *card_type, ev.damage);
+        }
+    }
+
+    for ev in ev_played.iter() {
+        if let Ok((card_type, Some(_))) = q_card.get(ev.entity) {
+            stats.record_play(*card_type);
+        }
+    }
+
+    for (damage, player) in q_damaged.iter() {
+        let damage = damage.0.max(0) as u32;
+
+        if player.is_some() {
+            stats.damage_taken += damage;
+        } else {
+            stats.damage_dealt += damage;
+        }
+    }
+
+    for player in q_killed.iter() {
+        if player.is_some() {
+            stats.cards_lost += 1;
+        } else {
+            stats.cards_destroyed += 1;
+        }
+    }
+}
+
+fn record_daily(
+    current_state: Res<State<GameState>>,
+    config: Res<GameConfig>,
+    player: Res<PlayerState>,
+    profile: Res<Profile>,
+    settings: Res<Settings>,
+    mut leaderboard: ResMut<Leaderboard>,
+) {
+    if let Some(day) = config.daily {
+        leaderboard.record(
+            &profile.id,
+            &settings.player_name,
+            day,
+            current_state.0 == GameState::Win,
+            player.turn,
+            player.health,
+        );
+        leaderboard.save();
+    }
+}
+
+fn record_profile(
+    current_state: Res<State<GameState>>,
+    config: Res<GameConfig>,
+    player: Res<PlayerState>,
+    puzzle: Option<Res<ActivePuzzle>>,
+    stats: Res<MatchStats>,
+    tutorial: Option<Res<Tutorial>>,
+    mut profile: ResMut<Profile>,
+) {
+    if puzzle.is_some() || tutorial.is_some() {
+        return;
+    }
+
+    let outcome = match current_state.0 {
+        GameState::Win => Outcome::Win,
+        GameState::Lose => Outcome::Lose,
+        _ => Outcome::Draw,
+    };
+
+    profile.record_game(config.selection, outcome, player.turn, stats.cards_played());
+    profile.save();
+}
+
 fn reset_dial(mut q_dial: Query<&mut Transform, With<TurnDial>>) {
     for mut transform in q_dial.iter_mut() {
         *transform = transform.with_rotation(Quat::from_rotation_y(0.0));
@@ -1174,13 +2994,166 @@ fn reset_power<C: Component + Default, P: PlayableState>(
     }
 }
 
+fn reset_undo_stack(mut undo: ResMut<UndoStack>) {
+    undo.placements.clear();
+}
+
+fn reshuffle_graveyard(
+    mut commands: Commands,
+    card_assets: Res<CardAssets>,
+    config: Res<GameConfig>,
+    mut player_state: ResMut<PlayerState>,
+) {
+    if config.deck_out != DeckOutRule::Reshuffle || player_state.deck_size() > 0 {
+        return;
+    }
+
+    player_state.reshuffle_graveyard();
+    spawn_deck(&mut commands, &card_assets, player_state.deck_size());
+}
+
+fn resurrect(
+    mut commands: Commands,
+    card_assets: Res<CardAssets>,
+    settings: Res<Settings>,
+    mut board: ResMut<PlayerBoard>,
+    mut player_state: ResMut<PlayerState>,
+    mut undo: ResMut<UndoStack>,
+    mut ev_played: EventReader<CardPlayedEvent>,
+    q_card: Query<&CardType, With<Player>>,
+    q_hand: Query<(Entity, &Hand)>,
+    q_placeholder: Query<(&CardPlaceholder, &Transform), With<Player>>,
+    mut q_cards: Query<(&CardType, &mut StatModifiers, &mut Attack, &mut Health), With<Player>>,
+) {
+    for ev in ev_played.iter() {
+        if !matches!(
+            q_card.get(ev.entity).map(|card| card.ability()),
+            Ok(CardAbility::Resurrect)
+        ) {
+            continue;
+        }
+
+        let lane = board.state().empty_place_near(ev.entity);
+        let hand = q_hand
+            .iter()
+            .filter(|(entity, _)| *entity != ev.entity)
+            .collect::<Vec<_>>();
+
+        if lane.is_none() && hand.len() as u32 >= player_state.max_hand_size {
+            continue;
+        }
+
+        let card_type = if let Some(card_type) = player_state.resurrect() {
+            card_type
+        } else {
+            continue;
+        };
+
+        // The resurrected card is revealed, so earlier placements can no longer be taken back.
+        undo.placements.clear();
+
+        if let Some(lane) = lane {
+            let (_, transform) = q_placeholder.iter().find(|(p, _)| p.0 == lane).unwrap();
+            let entity = spawn_board_card::<Player>(
+                &mut commands,
+                &card_assets,
+                &settings,
+                card_type,
+                PlayerState::discard_pile_translation(),
+                *transform,
+            );
+            let mut modifiers = StatModifiers::default();
+
+            board.place(lane, entity, card_type);
+
+            for other in board.others(entity) {
+                if other
+                    .card_type
+                    .affects(other.entity, board.state())
+                    .contains(&entity)
+                {
+                    modifiers.add(other.entity, other.card_type.effect());
+                }
+            }
+
+            // The Phoenix that revived it only gets its `StatModifiers` once commands are
+            // applied, so it picks up this card's aura in `receive_ability` instead.
+            for target in card_type.affects(entity, board.state()) {
+                if let Ok((target_type, mut target_modifiers, mut attack, mut health)) =
+                    q_cards.get_mut(target)
+                {
+                    target_modifiers.add(entity, card_type.effect());
+                    target_modifiers.sync(*target_type, &mut attack, &mut health);
+                }
+            }
+
+            commands.entity(entity).insert((
+                modifiers.attack(card_type),
+                modifiers.health(card_type),
+                modifiers,
+            ));
+        } else {
+            let index = hand.iter().map(|(_, hand)| hand.0 + 1).max().unwrap_or(0);
+            let start = PlayerState::discard_pile_translation();
+            let end = Vec3::new(
+                hand.len() as f32 * CARD_WIDTH - 5.0,
+                CARD_HALF_THICKNESS,
+                HAND_Z,
+            );
+            let animation = settings.animation_duration(500).map(|duration| {
+                Tween::new(
+                    EaseFunction::QuadraticInOut,
+                    duration,
+                    TransformPositionLens { start, end },
+                )
+            });
+            let attributes = card_type.attributes();
+            let mesh = card_type.mesh(&card_assets);
+            let material = card_type.material(&card_assets);
+            let translation = if animation.is_some() { start } else { end };
+
+            let entity = commands
+                .spawn((
+                    PbrBundle {
+                        mesh: card_assets.card_mesh.clone(),
+                        material: card_assets.card_material.clone(),
+                        transform: Transform::from_translation(translation),
+                        ..default()
+                    },
+                    card_type,
+                    Hand(index),
+                    Attack(attributes.attack as i32),
+                    Cost(attributes.cost as i32),
+                    Health(attributes.health as i32),
+                    Player,
+                ))
+                .insert(PickableBundle::default())
+                .with_children(|parent| {
+                    parent.spawn(PbrBundle {
+                        mesh,
+                        material,
+                        ..default()
+                    });
+                })
+                .id();
+
+            if let Some(tween) = animation {
+                commands.entity(entity).insert(Animator::new(tween));
+            }
+        }
+    }
+}
+
 fn setup_game(
     mut commands: Commands,
     board_assets: Res<BoardAssets>,
     card_assets: Res<CardAssets>,
     opponent_state: Res<OpponentState>,
     player_state: Res<PlayerState>,
+    puzzle: Option<Res<ActivePuzzle>>,
+    tutorial: Option<Res<Tutorial>>,
     mut state: ResMut<NextState<GameState>>,
+    q_placeholder: Query<(&CardPlaceholder, &Transform, Option<&Player>)>,
 ) {
     const BLOCK_POSITIONS: [(f32, f32); 8] = [
         (0.0, BLOCK_SIZE),
@@ -1259,25 +3232,42 @@ fn setup_game(
         ));
     }
 
-    for i in 0..player_state.deck_size() {
-        let y = i as f32 * CARD_THICKNESS + CARD_HALF_THICKNESS;
+    commands.insert_resource(MatchStats::default());
+    commands.insert_resource(MulliganState::default());
+    commands.insert_resource(UndoStack::default());
 
-        commands.spawn((
-            PbrBundle {
-                mesh: card_assets.card_mesh.clone(),
-                material: card_assets.card_material.clone(),
-                transform: Transform::from_xyz(8.0, y, 5.0)
-                    .with_rotation(Quat::from_rotation_z(180.0_f32.to_radians())),
-                ..default()
-            },
-            Deck(i),
-            Player,
-        ));
+    // Puzzles skip the deck and mulligan and start on a prepared board.
+    if let Some(puzzle) = puzzle {
+        spawn_puzzle(&mut commands, &card_assets, &puzzle.0, &q_placeholder);
+        commands.remove_resource::<AchievementProgress>();
+        state.set(GameState::PlayerTurn);
+        return;
+    }
+
+    spawn_deck(&mut commands, &card_assets, player_state.deck_size());
+
+    if tutorial.is_some() {
+        commands.remove_resource::<AchievementProgress>();
+    } else {
+        commands.insert_resource(AchievementProgress::default());
     }
 
     commands.insert_resource(OpponentBoard::new());
     commands.insert_resource(PlayerBoard::new());
-    state.set(GameState::PlayerTurn);
+    state.set(GameState::StartGame.next().unwrap());
+}
+
+fn show_game_over_text(
+    outcome: GameOverText,
+    q_text: &mut Query<(&GameOverText, &mut Visibility), (Without<Camera>, Without<Menu>)>,
+) {
+    for (text, mut visibility) in q_text.iter_mut() {
+        *visibility = if *text == outcome {
+            Visibility::Visible
+        } else {
+            Visibility::Hidden
+        };
+    }
 }
 
 fn slide_hand(
@@ -1296,41 +3286,958 @@ fn slide_hand(
     }
 }
 
-fn spend_power(
-    card_assets: Res<CardAssets>,
-    mut ev_played: EventReader<CardPlayedEvent>,
-    mut player_state: ResMut<PlayerState>,
-    q_cost: Query<&Cost>,
-    mut q_power: Query<(&mut Power, &mut Handle<StandardMaterial>)>,
-) {
-    let mut power_spent = 0;
-
-    for ev in ev_played.iter() {
-        if let Ok(cost) = q_cost.get(ev.entity) {
-            player_state.available_power -= cost.get();
-            power_spent += cost.get();
-        }
+// Cards the game places on the board, rather than the player, slide in from `start`.
+fn spawn_board_card<C: Component + Default>(
+    commands: &mut Commands,
+    card_assets: &CardAssets,
+    settings: &Settings,
+    card_type: CardType,
+    start: Vec3,
+    transform: Transform,
+) -> Entity {
+    let end = transform.translation;
+    let animation = settings.animation_duration(600).map(|duration| {
+        Tween::new(
+            EaseFunction::QuadraticInOut,
+            duration,
+            TransformPositionLens { start, end },
+        )
+        .with_completed_event(TWEEN_EVENT_REMOVE_PERFORM_ACTION)
+    });
+    let translation = if animation.is_some() { start } else { end };
+    let attributes = card_type.attributes();
+    let entity = commands
+        .spawn((
+            PbrBundle {
+                mesh: card_assets.card_mesh.clone(),
+                material: card_assets.card_material.clone(),
+                transform: transform.with_translation(translation),
+                ..default()
+            },
+            card_type,
+            Attack(attributes.attack as i32),
+            Health(attributes.health as i32),
+            C::default(),
+            StatModifiers::default(),
+            PickableBundle::default(),
+        ))
+        .with_children(|parent| {
+            parent.spawn(PbrBundle {
+                mesh: card_type.mesh(card_assets),
+                material: card_type.material(card_assets),
+                ..default()
+            });
+        })
+        .id();
+
+    // Only animated cards get a completion event to clear `PerformingAction`.
+    if let Some(tween) = animation {
+        commands
+            .entity(entity)
+            .insert((Animator::new(tween), PerformingAction));
     }
 
-    if power_spent > 0 {
-        let mut power_vec = q_power
-            .iter_mut()
-            .filter(|(power, _)| power.available)
-            .collect::<Vec<_>>();
+    entity
+}
 
-        power_vec.sort_by(|a, b| a.0.index.partial_cmp(&b.0.index).unwrap());
+fn spawn_deck(commands: &mut Commands, card_assets: &CardAssets, size: u32) {
+    for i in 0..size {
+        let y = i as f32 * CARD_THICKNESS + CARD_HALF_THICKNESS;
 
-        for _ in 0..power_spent {
-            if let Some((mut power, mut material)) = power_vec.pop() {
-                power.available = false;
-                *material = card_assets.gem_empty_material.clone();
-            }
-        }
+        commands.spawn((
+            PbrBundle {
+                mesh: card_assets.card_mesh.clone(),
+                material: card_assets.card_material.clone(),
+                transform: Transform::from_translation(DECK_TRANSLATION + Vec3::new(0.0, y, 0.0))
+                    .with_rotation(Quat::from_rotation_z(180.0_f32.to_radians())),
+                ..default()
+            },
+            Deck(i),
+            Player,
+        ));
     }
 }
 
-fn update_player_health(
-    mut commands: Commands,
+fn spawn_menu_button(
+    commands: &mut Commands,
+    ui_assets: &UiAssets,
+    label: &str,
+    bottom: f32,
+    marker: impl Component,
+) {
+    commands.spawn((
+        TextBundle::from_section(
+            label,
+            TextStyle {
+                font: ui_assets.font.clone(),
+                font_size: 24.0,
+                color: Color::WHITE,
+            },
+        )
+        .with_style(Style {
+            position_type: PositionType::Absolute,
+            position: UiRect {
+                bottom: Val::Px(bottom),
+                left: Val::Px(16.0),
+                ..default()
+            },
+            ..default()
+        }),
+        Interaction::default(),
+        MenuButton,
+        marker,
+    ));
+}
+
+fn spawn_profile_screen(mut commands: Commands, ui_assets: Res<UiAssets>, profile: Res<Profile>) {
+    let style = TextStyle {
+        font: ui_assets.font.clone(),
+        font_size: 20.0,
+        color: Color::WHITE,
+    };
+
+    commands
+        .spawn((
+            NodeBundle {
+                background_color: Color::rgba(0.0, 0.0, 0.0, 0.9).into(),
+                style: Style {
+                    flex_direction: FlexDirection::Column,
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    position_type: PositionType::Absolute,
+                    size: Size::new(Val::Percent(100.0), Val::Percent(100.0)),
+                    ..default()
+                },
+                z_index: ZIndex::Global(10),
+                ..default()
+            },
+            ProfileScreen,
+        ))
+        .with_children(|parent| {
+            parent.spawn(TextBundle::from_section(profile.summary(), style.clone()));
+            parent.spawn((
+                TextBundle::from_section("Back", style).with_style(Style {
+                    margin: UiRect::top(Val::Px(16.0)),
+                    ..default()
+                }),
+                Interaction::default(),
+                ProfileBack,
+            ));
+        });
+}
+
+fn spawn_puzzle(
+    commands: &mut Commands,
+    card_assets: &CardAssets,
+    puzzle: &Puzzle,
+    q_placeholder: &Query<(&CardPlaceholder, &Transform, Option<&Player>)>,
+) {
+    let mut opponent_board = OpponentBoard::new();
+    let mut player_board = PlayerBoard::new();
+    let mut spawned = Vec::new();
+
+    for (placeholder, transform, player) in q_placeholder.iter() {
+        let index = placeholder.0;
+        let card = if player.is_some() {
+            puzzle.player_board[index as usize]
+        } else {
+            puzzle.opponent_board[index as usize]
+        };
+        let card = if let Some(card) = card {
+            card
+        } else {
+            continue;
+        };
+        let entity = commands
+            .spawn((
+                PbrBundle {
+                    mesh: card_assets.card_mesh.clone(),
+                    material: card_assets.card_material.clone(),
+                    transform: *transform,
+                    ..default()
+                },
+                card.card_type,
+                PickableBundle::default(),
+            ))
+            .with_children(|parent| {
+                parent.spawn(PbrBundle {
+                    mesh: card.card_type.mesh(card_assets),
+                    material: card.card_type.material(card_assets),
+                    ..default()
+                });
+            })
+            .id();
+
+        if player.is_some() {
+            commands.entity(entity).insert(Player);
+            player_board.place(index, entity, card.card_type);
+        } else {
+            commands.entity(entity).insert(Opponent);
+            opponent_board.place(index, entity, card.card_type);
+        }
+
+        spawned.push((entity, card, player.is_some()));
+    }
+
+    for (entity, card, player) in spawned.iter() {
+        let board = if *player {
+            player_board.state()
+        } else {
+            opponent_board.state()
+        };
+        let mut modifiers = StatModifiers::default();
+
+        for (source, source_card, _) in spawned.iter().filter(|(_, _, other)| other == player) {
+            if source_card
+                .card_type
+                .affects(*source, board)
+                .contains(entity)
+            {
+                modifiers.add(*source, source_card.card_type.effect());
+            }
+        }
+
+        // Whatever the authored stats add on top of base stats and auras is kept as puzzle setup.
+        let effect = CardAbilityEffect {
+            attack: card.attack - modifiers.attack(card.card_type).0,
+            health: card.health - modifiers.health(card.card_type).0,
+        };
+
+        modifiers.add_from(ModifierSource::Puzzle, effect);
+        commands.entity(*entity).insert((
+            modifiers.attack(card.card_type),
+            modifiers.health(card.card_type),
+            modifiers,
+        ));
+    }
+
+    for (i, card_type) in puzzle.hand.iter().enumerate() {
+        let attributes = card_type.attributes();
+
+        commands
+            .spawn((
+                PbrBundle {
+                    mesh: card_assets.card_mesh.clone(),
+                    material: card_assets.card_material.clone(),
+                    transform: Transform::from_xyz(
+                        i as f32 * CARD_WIDTH - 5.0,
+                        CARD_HALF_THICKNESS,
+                        HAND_Z,
+                    ),
+                    ..default()
+                },
+                *card_type,
+                Hand(i as u32),
+                Attack(attributes.attack as i32),
+                Cost(attributes.cost as i32),
+                Health(attributes.health as i32),
+                Player,
+                PickableBundle::default(),
+            ))
+            .with_children(|parent| {
+                parent.spawn(PbrBundle {
+                    mesh: card_type.mesh(card_assets),
+                    material: card_type.material(card_assets),
+                    ..default()
+                });
+            });
+    }
+
+    commands.insert_resource(opponent_board);
+    commands.insert_resource(player_board);
+}
+
+fn spawn_puzzle_screen(mut commands: Commands, ui_assets: Res<UiAssets>) {
+    let style = TextStyle {
+        font: ui_assets.font.clone(),
+        font_size: 20.0,
+        color: Color::WHITE,
+    };
+
+    commands
+        .spawn((
+            NodeBundle {
+                background_color: Color::rgba(0.0, 0.0, 0.0, 0.9).into(),
+                style: Style {
+                    flex_direction: FlexDirection::Column,
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    position_type: PositionType::Absolute,
+                    size: Size::new(Val::Percent(100.0), Val::Percent(100.0)),
+                    ..default()
+                },
+                z_index: ZIndex::Global(10),
+                ..default()
+            },
+            PuzzleScreen,
+        ))
+        .with_children(|parent| {
+            parent.spawn(TextBundle::from_section("Puzzles", style.clone()));
+
+            for (index, puzzle) in Puzzle::bundled().iter().enumerate() {
+                parent.spawn((
+                    TextBundle::from_section(
+                        format!(
+                            "{}. {} - {}\n{}",
+                            index + 1,
+                            puzzle.name,
+                            puzzle.goal.description(),
+                            puzzle.description
+                        ),
+                        style.clone(),
+                    )
+                    .with_style(Style {
+                        margin: UiRect::top(Val::Px(16.0)),
+                        ..default()
+                    }),
+                    Interaction::default(),
+                    PuzzleOption::Play(index),
+                ));
+            }
+
+            parent.spawn((
+                TextBundle::from_section("Back", style).with_style(Style {
+                    margin: UiRect::top(Val::Px(16.0)),
+                    ..default()
+                }),
+                Interaction::default(),
+                PuzzleOption::Back,
+            ));
+        });
+}
+
+fn spawn_results(
+    commands: &mut Commands,
+    ui_assets: &UiAssets,
+    summary: String,
+    leaderboard: Option<String>,
+) {
+    let style = TextStyle {
+        font: ui_assets.font.clone(),
+        font_size: 20.0,
+        color: Color::WHITE,
+    };
+
+    commands
+        .spawn((
+            NodeBundle {
+                background_color: Color::rgba(0.0, 0.0, 0.0, 0.75).into(),
+                style: Style {
+                    flex_direction: FlexDirection::Column,
+                    position_type: PositionType::Absolute,
+                    position: UiRect {
+                        top: Val::Px(16.0),
+                        left: Val::Px(16.0),
+                        ..default()
+                    },
+                    padding: UiRect::all(Val::Px(12.0)),
+                    ..default()
+                },
+                ..default()
+            },
+            ResultsScreen,
+        ))
+        .with_children(|parent| {
+            parent.spawn(TextBundle::from_section(summary, style.clone()));
+
+            if let Some(leaderboard) = leaderboard {
+                parent.spawn((
+                    TextBundle::from_section(leaderboard, style.clone()).with_style(Style {
+                        margin: UiRect::top(Val::Px(8.0)),
+                        ..default()
+                    }),
+                    LeaderboardText,
+                ));
+
+                for (label, button) in [
+                    ("Export leaderboard", LeaderboardButton::Export),
+                    ("Import leaderboard", LeaderboardButton::Import),
+                ] {
+                    parent.spawn((
+                        TextBundle::from_section(label, style.clone()).with_style(Style {
+                            margin: UiRect::top(Val::Px(8.0)),
+                            ..default()
+                        }),
+                        Interaction::default(),
+                        button,
+                    ));
+                }
+            }
+
+            for (label, button) in [
+                ("Rematch with same seed", ResultsButton::Rematch),
+                ("Main menu", ResultsButton::MainMenu),
+            ] {
+                parent.spawn((
+                    TextBundle::from_section(label, style.clone()).with_style(Style {
+                        margin: UiRect::top(Val::Px(8.0)),
+                        ..default()
+                    }),
+                    Interaction::default(),
+                    button,
+                ));
+            }
+        });
+}
+
+fn spawn_settings_menu(
+    mut commands: Commands,
+    ui_assets: Res<UiAssets>,
+    settings: Res<Settings>,
+    editing_name: Res<EditingName>,
+    rebinding: Res<Rebinding>,
+) {
+    commands
+        .spawn((
+            NodeBundle {
+                background_color: Color::rgba(0.0, 0.0, 0.0, 0.9).into(),
+                style: Style {
+                    flex_direction: FlexDirection::Column,
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    position_type: PositionType::Absolute,
+                    size: Size::new(Val::Percent(100.0), Val::Percent(100.0)),
+                    ..default()
+                },
+                z_index: ZIndex::Global(10),
+                ..default()
+            },
+            SettingsMenu,
+        ))
+        .with_children(|parent| {
+            for option in SettingsOption::all() {
+                parent.spawn((
+                    TextBundle::from_section(
+                        option.label(&settings, rebinding.0, editing_name.0),
+                        TextStyle {
+                            font: ui_assets.font.clone(),
+                            font_size: 20.0,
+                            color: Color::WHITE,
+                        },
+                    )
+                    .with_style(Style {
+                        margin: UiRect::all(Val::Px(2.0)),
+                        ..default()
+                    }),
+                    Interaction::default(),
+                    option,
+                ));
+            }
+        });
+}
+
+fn spawn_toast(commands: &mut Commands, ui_assets: &UiAssets, index: usize, text: String) {
+    commands.spawn((
+        TextBundle {
+            background_color: Color::rgba(0.0, 0.0, 0.0, 0.75).into(),
+            ..TextBundle::from_section(
+                text,
+                TextStyle {
+                    font: ui_assets.font.clone(),
+                    font_size: 20.0,
+                    color: Color::GOLD,
+                },
+            )
+            .with_style(Style {
+                position_type: PositionType::Absolute,
+                // Toasts stack below the attack forecast panel.
+                position: UiRect {
+                    top: Val::Px(256.0 + index as f32 * 64.0),
+                    right: Val::Px(16.0),
+                    ..default()
+                },
+                padding: UiRect::all(Val::Px(8.0)),
+                ..default()
+            })
+        },
+        AchievementToast::default(),
+    ));
+}
+
+fn spend_power(
+    card_assets: Res<CardAssets>,
+    mut ev_played: EventReader<CardPlayedEvent>,
+    mut player_state: ResMut<PlayerState>,
+    q_cost: Query<&Cost>,
+    mut q_power: Query<(&mut Power, &mut Handle<StandardMaterial>)>,
+) {
+    let mut power_spent = 0;
+
+    for ev in ev_played.iter() {
+        if let Ok(cost) = q_cost.get(ev.entity) {
+            player_state.available_power -= cost.get();
+            power_spent += cost.get();
+        }
+    }
+
+    if power_spent > 0 {
+        let mut power_vec = q_power
+            .iter_mut()
+            .filter(|(power, _)| power.available)
+            .collect::<Vec<_>>();
+
+        power_vec.sort_by(|a, b| a.0.index.partial_cmp(&b.0.index).unwrap());
+
+        for _ in 0..power_spent {
+            if let Some((mut power, mut material)) = power_vec.pop() {
+                power.available = false;
+                *material = card_assets.gem_empty_material.clone();
+            }
+        }
+    }
+}
+
+fn start_puzzle(commands: &mut Commands, puzzle: Puzzle) {
+    commands.insert_resource(puzzle.game_config());
+    commands.insert_resource(puzzle.opponent_state());
+    commands.insert_resource(puzzle.player_state());
+    commands.insert_resource(ActivePuzzle(puzzle));
+    commands.remove_resource::<Tutorial>();
+}
+
+fn start_tutorial(commands: &mut Commands) {
+    let tutorial = Tutorial::load();
+
+    commands.insert_resource(tutorial.game_config());
+    commands.insert_resource(tutorial.opponent_state());
+    commands.insert_resource(tutorial.player_state());
+    commands.insert_resource(tutorial);
+    commands.remove_resource::<ActivePuzzle>();
+}
+
+fn step_focus(entities: &[Entity], current: Option<Entity>, forward: bool) -> Option<Entity> {
+    let last = entities.len().checked_sub(1)?;
+    let index = match (
+        current.and_then(|current| entities.iter().position(|entity| *entity == current)),
+        forward,
+    ) {
+        (Some(index), true) => (index + 1).min(last),
+        (Some(index), false) => index.saturating_sub(1),
+        (None, true) => 0,
+        (None, false) => last,
+    };
+
+    entities.get(index).copied()
+}
+
+fn track_achievements(
+    mut commands: Commands,
+    ui_assets: Res<UiAssets>,
+    config: Res<GameConfig>,
+    current_state: Res<State<GameState>>,
+    board: Res<PlayerBoard>,
+    mut progress: ResMut<AchievementProgress>,
+    mut profile: ResMut<Profile>,
+    mut ev_attacked: EventReader<AttackedEvent>,
+    q_killed: Query<Entity, (With<Player>, With<CardType>, Added<Killed>)>,
+    q_toast: Query<With<AchievementToast>>,
+) {
+    if current_state.is_changed() {
+        match current_state.0 {
+            GameState::PlayerTurn => progress.tower_damage_this_turn = 0,
+            GameState::Win => {
+                progress.board = board.all().iter().map(|placed| placed.card_type).collect();
+                progress.won_on = Some(config.selection);
+            }
+            _ => {}
+        }
+    }
+
+    for ev in ev_attacked.iter() {
+        match ev {
+            AttackedEvent::Opponent(damage, DamageSource::Attack) => {
+                progress.tower_damage_this_turn += damage
+            }
+            AttackedEvent::Opponent(_, DamageSource::Fatigue) => {}
+            AttackedEvent::Player(damage, _) => progress.hearts_lost += damage,
+        }
+    }
+
+    progress.cards_lost += q_killed.iter().count() as u32;
+
+    let mut toasts = q_toast.iter().count();
+
+    for achievement in ACHIEVEMENTS.iter() {
+        if !achievement.condition.met(&progress) || !profile.unlock(achievement) {
+            continue;
+        }
+
+        profile.save();
+        spawn_toast(
+            &mut commands,
+            &ui_assets,
+            toasts,
+            format!(
+                "Achievement unlocked: {}\n{}",
+                achievement.name, achievement.description
+            ),
+        );
+        toasts += 1;
+    }
+}
+
+fn track_card_unlocks(
+    mut commands: Commands,
+    ui_assets: Res<UiAssets>,
+    mut profile: ResMut<Profile>,
+    q_toast: Query<With<AchievementToast>>,
+) {
+    if !profile.is_changed() {
+        return;
+    }
+
+    // Checking must not mark the profile changed again or this would run every frame.
+    let unlocked = profile.bypass_change_detection().unlock_cards();
+
+    if unlocked.is_empty() {
+        return;
+    }
+
+    profile.set_changed();
+    profile.save();
+
+    let toasts = q_toast.iter().count();
+
+    for (i, card_type) in unlocked.iter().enumerate() {
+        spawn_toast(
+            &mut commands,
+            &ui_assets,
+            toasts + i,
+            format!(
+                "Card unlocked: {}\nNow shuffled into your deck",
+                card_type.name()
+            ),
+        );
+    }
+}
+
+fn undo_placement(
+    mut commands: Commands,
+    card_assets: Res<CardAssets>,
+    mut board: ResMut<PlayerBoard>,
+    mut player_state: ResMut<PlayerState>,
+    mut stats: ResMut<MatchStats>,
+    tutorial: Option<Res<Tutorial>>,
+    mut undo: ResMut<UndoStack>,
+    mut ev_action: EventReader<InputAction>,
+    mut q_cards: Query<
+        (
+            Entity,
+            &CardType,
+            &mut StatModifiers,
+            &mut Attack,
+            &mut Health,
+        ),
+        With<Player>,
+    >,
+    q_cost: Query<&Cost>,
+    mut q_hand: Query<(&Hand, &mut Transform)>,
+    mut q_transform: Query<&mut Transform, (With<CardType>, Without<Hand>)>,
+    mut q_power: Query<(&mut Power, &mut Handle<StandardMaterial>)>,
+) {
+    if !ev_action.iter().any(|action| *action == InputAction::Undo)
+        || tutorial.map_or(false, |tutorial| tutorial.locked())
+    {
+        return;
+    }
+
+    let played = if let Some(played) = undo.placements.pop() {
+        played
+    } else {
+        return;
+    };
+
+    for (entity, card_type, mut modifiers, mut attack, mut health) in q_cards.iter_mut() {
+        if entity == played.entity {
+            *modifiers = StatModifiers::default();
+            stats.unrecord_play(*card_type);
+        } else if modifiers.contains(played.entity) {
+            modifiers.remove(played.entity);
+        } else {
+            continue;
+        }
+
+        modifiers.sync(*card_type, &mut attack, &mut health);
+    }
+
+    board.remove(played.entity);
+
+    let mut slot = 0;
+
+    for (hand, mut transform) in q_hand.iter_mut() {
+        if hand.0 < played.hand {
+            slot += 1;
+        } else {
+            transform.translation.x += CARD_WIDTH;
+        }
+    }
+
+    if let Ok(mut transform) = q_transform.get_mut(played.entity) {
+        transform.translation =
+            Vec3::new(slot as f32 * CARD_WIDTH - 5.0, CARD_HALF_THICKNESS, HAND_Z);
+    }
+
+    commands
+        .entity(played.entity)
+        .remove::<StatModifiers>()
+        .insert(Hand(played.hand));
+
+    let cost = q_cost.get(played.entity).map_or(0, |cost| cost.get());
+    let mut power_vec = q_power
+        .iter_mut()
+        .filter(|(power, _)| !power.available)
+        .collect::<Vec<_>>();
+
+    power_vec.sort_by(|a, b| b.0.index.cmp(&a.0.index));
+    player_state.available_power += cost;
+
+    for _ in 0..cost {
+        if let Some((mut power, mut material)) = power_vec.pop() {
+            power.available = true;
+            *material = card_assets.gem_material.clone();
+        }
+    }
+}
+
+fn update_achievement_toasts(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut q_toast: Query<(Entity, &mut AchievementToast)>,
+) {
+    for (entity, mut toast) in q_toast.iter_mut() {
+        if toast.timer.tick(time.delta()).finished() {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
+
+fn update_discard_pile<C: Component + Default, S: PlayableState>(
+    mut commands: Commands,
+    card_assets: Res<CardAssets>,
+    player_state: Res<S>,
+    q_pile: Query<(Entity, &DiscardPile), With<C>>,
+) {
+    if !player_state.is_changed() {
+        return;
+    }
+
+    let graveyard = player_state.graveyard();
+    let mut pile = q_pile.iter().collect::<Vec<_>>();
+
+    pile.sort_by(|(_, pile_a), (_, pile_b)| pile_a.0.cmp(&pile_b.0));
+
+    while pile.len() as u32 > graveyard.size() {
+        if let Some((entity, _)) = pile.pop() {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+
+    for (i, fallen) in graveyard.cards().iter().enumerate().skip(pile.len()) {
+        let y = i as f32 * CARD_THICKNESS + CARD_HALF_THICKNESS;
+
+        commands
+            .spawn((
+                PbrBundle {
+                    mesh: card_assets.card_mesh.clone(),
+                    material: card_assets.card_material.clone(),
+                    transform: Transform::from_translation(
+                        S::discard_pile_translation() + Vec3::new(0.0, y, 0.0),
+                    ),
+                    ..default()
+                },
+                DiscardPile(i as u32),
+                C::default(),
+            ))
+            .with_children(|parent| {
+                parent.spawn(PbrBundle {
+                    mesh: fallen.card_type.mesh(&card_assets),
+                    material: fallen.card_type.material(&card_assets),
+                    ..default()
+                });
+            });
+    }
+}
+
+fn update_focus_cursor(
+    focus: Res<Focus>,
+    q_focused: Query<&GlobalTransform, Without<FocusCursor>>,
+    mut q_cursor: Query<(&mut Transform, &mut Visibility), With<FocusCursor>>,
+) {
+    const CURSOR_OFFSET: Vec3 = Vec3::new(0.0, 0.5, CARD_HEIGHT / 2.0 + 0.4);
+
+    for (mut transform, mut visibility) in q_cursor.iter_mut() {
+        if let Some(focused) = focus.0.and_then(|entity| q_focused.get(entity).ok()) {
+            transform.translation = focused.translation() + CURSOR_OFFSET;
+            *visibility = Visibility::Inherited;
+        } else {
+            *visibility = Visibility::Hidden;
+        }
+    }
+}
+
+fn update_forecast(
+    current_state: Res<State<GameState>>,
+    player_board: Res<PlayerBoard>,
+    opponent_board: Res<OpponentBoard>,
+    mut ev_pick: EventReader<PickingEvent>,
+    q_dial: Query<With<TurnDial>>,
+    q_stats: Query<(&Attack, &Health)>,
+    mut q_forecast: Query<(&mut ForecastPanel, &mut Text, &mut Visibility)>,
+) {
+    for ev in ev_pick.iter() {
+        for (mut panel, _, _) in q_forecast.iter_mut() {
+            match ev {
+                PickingEvent::Hover(HoverEvent::JustEntered(e)) if q_dial.get(*e).is_ok() => {
+                    panel.hovered = true;
+                }
+                PickingEvent::Hover(HoverEvent::JustLeft(e)) if q_dial.get(*e).is_ok() => {
+                    panel.hovered = false;
+                }
+                _ => {}
+            }
+        }
+    }
+
+    for (panel, mut text, mut visibility) in q_forecast.iter_mut() {
+        if !panel.hovered || current_state.0 != GameState::PlayerTurn {
+            *visibility = Visibility::Hidden;
+            continue;
+        }
+
+        let forecast = AttackForecast::new(&*player_board, &*opponent_board, |entity| {
+            q_stats
+                .get(entity)
+                .map_or((0, 0), |(attack, health)| (attack.0, health.0))
+        });
+        let mut lines = vec!["Attack forecast".to_string()];
+
+        for lane in forecast.lanes.iter() {
+            let target = match (lane.target, lane.kills) {
+                (Some(target), true) => format!("destroys {}", target.name()),
+                (Some(target), false) => format!("hits {}", target.name()),
+                (None, _) => "hits the tower".to_string(),
+            };
+
+            lines.push(format!(
+                "Lane {}: {} damage, {target}",
+                lane.lane + 1,
+                lane.damage
+            ));
+        }
+
+        lines.push(format!("Tower damage: {}", forecast.tower_damage));
+        lines.push(format!(
+            "Expected damage to you: {}",
+            forecast.counter_damage
+        ));
+        lines.push(format!(
+            "Expected damage to your cards: {}",
+            forecast.card_counter_damage
+        ));
+
+        let description = lines.join("\n");
+
+        if text.sections[0].value != description {
+            text.sections[0].value = description;
+        }
+
+        *visibility = Visibility::Inherited;
+    }
+}
+
+fn update_deck_out_button(
+    settings: Res<Settings>,
+    mut q_button: Query<&mut Text, With<DeckOutButton>>,
+) {
+    if !settings.is_changed() {
+        return;
+    }
+
+    for mut text in q_button.iter_mut() {
+        text.sections[0].value = SettingsOption::DeckOut.label(&settings, None, false);
+    }
+}
+
+fn update_menu_buttons(
+    current_state: Res<State<GameState>>,
+    player: Option<Res<PlayerState>>,
+    mut q_button: Query<&mut Visibility, With<MenuButton>>,
+) {
+    let target = if menu_available(current_state.0, player.as_deref()) {
+        Visibility::Inherited
+    } else {
+        Visibility::Hidden
+    };
+
+    for mut visibility in q_button.iter_mut() {
+        if *visibility != target {
+            *visibility = target;
+        }
+    }
+}
+
+fn update_opponent_hand(
+    mut commands: Commands,
+    card_assets: Res<CardAssets>,
+    opponent_state: Res<OpponentState>,
+    q_hand: Query<(Entity, &OpponentHand)>,
+) {
+    const OPPONENT_HAND_Z: f32 = -9.0;
+
+    if !opponent_state.is_changed() {
+        return;
+    }
+
+    let mut hand = q_hand.iter().collect::<Vec<_>>();
+
+    hand.sort_by(|(_, hand_a), (_, hand_b)| hand_a.0.cmp(&hand_b.0));
+
+    while hand.len() as u32 > opponent_state.hand_size() {
+        if let Some((entity, _)) = hand.pop() {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+
+    for i in hand.len() as u32..opponent_state.hand_size() {
+        let x = i as f32 * CARD_WIDTH - 5.0;
+
+        commands.spawn((
+            PbrBundle {
+                mesh: card_assets.card_mesh.clone(),
+                material: card_assets.card_material.clone(),
+                transform: Transform::from_xyz(x, CARD_HALF_THICKNESS, OPPONENT_HAND_Z)
+                    .with_rotation(Quat::from_rotation_z(180.0_f32.to_radians())),
+                ..default()
+            },
+            OpponentHand(i),
+        ));
+    }
+}
+
+fn update_phase_banner(
+    time: Res<Time>,
+    current_state: Res<State<GameState>>,
+    mut q_banner: Query<(&mut PhaseBanner, &mut Text, &mut Visibility)>,
+) {
+    for (mut banner, mut text, mut visibility) in q_banner.iter_mut() {
+        if current_state.is_changed() {
+            if let Some(title) = current_state.0.banner() {
+                text.sections[0].value = title.to_string();
+                banner.timer.reset();
+                *visibility = Visibility::Inherited;
+            } else {
+                *visibility = Visibility::Hidden;
+            }
+        }
+
+        if banner.timer.tick(time.delta()).just_finished() {
+            *visibility = Visibility::Hidden;
+        }
+    }
+}
+
+fn update_player_health(
+    mut commands: Commands,
     mut ev_attacked: EventReader<AttackedEvent>,
     q_block: Query<(Entity, &PlayerHealth), With<Opponent>>,
     q_hearts: Query<(Entity, &PlayerHealth), (With<Player>, Without<Opponent>)>,
@@ -1340,7 +4247,7 @@ fn update_player_health(
 
     for ev in ev_attacked.iter() {
         match ev {
-            AttackedEvent::Opponent(damage) => {
+            AttackedEvent::Opponent(damage, _) => {
                 if blocks.is_none() {
                     let mut blocks_vec = q_block.iter().collect::<Vec<_>>();
 
@@ -1356,7 +4263,7 @@ fn update_player_health(
                     }
                 }
             }
-            AttackedEvent::Player(damage) => {
+            AttackedEvent::Player(damage, _) => {
                 if hearts.is_none() {
                     let mut hearts_vec = q_hearts.iter().collect::<Vec<_>>();
 
@@ -1376,6 +4283,166 @@ fn update_player_health(
     }
 }
 
+fn update_stats_panel(
+    current_state: Res<State<GameState>>,
+    player: Res<PlayerState>,
+    opponent: Res<OpponentState>,
+    q_hand: Query<With<Hand>>,
+    mut q_panel: Query<(&mut Text, &mut Visibility), With<StatsPanel>>,
+) {
+    let in_game = matches!(
+        current_state.0,
+        GameState::StartGame
+            | GameState::Mulligan
+            | GameState::PlayerTurn
+            | GameState::PlayerAttacking
+            | GameState::OpponentPlayCards
+            | GameState::OpponentTurn
+            | GameState::OpponentAttacking
+    );
+
+    for (mut text, mut visibility) in q_panel.iter_mut() {
+        if !in_game {
+            *visibility = Visibility::Hidden;
+            continue;
+        }
+
+        let lines = [
+            format!("Turn {}", player.turn),
+            format!("Health {}/{}", player.health, player.get_max_health()),
+            format!("Power {}/{}", player.available_power, player.power),
+            format!(
+                "Hand {} / Deck {}",
+                q_hand.iter().count(),
+                player.deck_size()
+            ),
+            format!(
+                "Tower {}/{}",
+                opponent.get_health(),
+                opponent.get_max_health()
+            ),
+            format!(
+                "Opponent hand {} / Deck {}",
+                opponent.hand_size(),
+                opponent.deck_size()
+            ),
+        ];
+        let stats = lines.join("\n");
+
+        if text.sections[0].value != stats {
+            text.sections[0].value = stats;
+        }
+
+        *visibility = Visibility::Inherited;
+    }
+}
+
+fn update_thinking_indicator(
+    time: Res<Time>,
+    current_state: Res<State<GameState>>,
+    mut q_indicator: Query<(&mut Text, &mut Visibility), With<ThinkingIndicator>>,
+) {
+    let thinking = matches!(
+        current_state.0,
+        GameState::OpponentPlayCards | GameState::OpponentTurn
+    );
+
+    let target = if thinking {
+        Visibility::Inherited
+    } else {
+        Visibility::Hidden
+    };
+    let dots = (time.elapsed_seconds() * 2.0) as usize % 4;
+    let message = format!("Opponent is thinking{}", ".".repeat(dots));
+
+    for (mut text, mut visibility) in q_indicator.iter_mut() {
+        if *visibility != target {
+            *visibility = target;
+        }
+
+        if thinking && text.sections[0].value != message {
+            text.sections[0].value = message.clone();
+        }
+    }
+}
+
+fn update_tooltip(
+    q_cards: Query<(
+        &CardType,
+        &Attack,
+        &Health,
+        Option<&Cost>,
+        Option<&StatModifiers>,
+    )>,
+    q_sources: Query<&CardType>,
+    mut q_tooltip: Query<(&Tooltip, &mut Visibility)>,
+    mut q_text: Query<&mut Text, With<TooltipText>>,
+) {
+    for (tooltip, mut visibility) in q_tooltip.iter_mut() {
+        let card = tooltip.0.and_then(|entity| q_cards.get(entity).ok());
+
+        let (card_type, attack, health, cost, modifiers) = if let Some(card) = card {
+            card
+        } else {
+            *visibility = Visibility::Hidden;
+            continue;
+        };
+
+        let attributes = card_type.attributes();
+        let stat_line = |name: &str, stat: Stat, current: i32, base: u32| {
+            let mut line = format!("{name} {current} (base {base}");
+
+            for modifier in modifiers
+                .iter()
+                .flat_map(|modifiers| modifiers.modifiers(stat))
+            {
+                let source = match modifier.source {
+                    ModifierSource::Card(source) => q_sources
+                        .get(source)
+                        .map_or("unknown", |source| source.name()),
+                    ModifierSource::Puzzle => "puzzle setup",
+                };
+
+                line += &format!(", {:+} from {source}", modifier.delta);
+            }
+
+            line + ")"
+        };
+        let cost = cost.map_or(attributes.cost as i32, |cost| cost.0);
+        let description = [
+            card_type.name().to_string(),
+            stat_line("Attack", Stat::Attack, attack.0, attributes.attack),
+            stat_line("Health", Stat::Health, health.0, attributes.health),
+            format!("Cost {cost} (base {})", attributes.cost),
+            card_type.ability().description().to_string(),
+        ]
+        .join("\n");
+
+        for mut text in q_text.iter_mut() {
+            if text.sections[0].value != description {
+                text.sections[0].value = description.clone();
+            }
+        }
+
+        *visibility = Visibility::Inherited;
+    }
+}
+
+fn update_settings_menu(
+    settings: Res<Settings>,
+    editing_name: Res<EditingName>,
+    rebinding: Res<Rebinding>,
+    mut q_option: Query<(&SettingsOption, &mut Text)>,
+) {
+    if !settings.is_changed() && !editing_name.is_changed() && !rebinding.is_changed() {
+        return;
+    }
+
+    for (option, mut text) in q_option.iter_mut() {
+        text.sections[0].value = option.label(&settings, rebinding.0, editing_name.0);
+    }
+}
+
 fn update_sigils<A: Attribute + Component, S: Sigil + Component>(
     mut commands: Commands,
     card_assets: Res<CardAssets>,
@@ -1434,3 +4501,67 @@ fn update_sigils<A: Attribute + Component, S: Sigil + Component>(
         }
     }
 }
+
+fn update_tutorial(
+    tutorial: Option<Res<Tutorial>>,
+    q_target: Query<&GlobalTransform, Without<TutorialHighlight>>,
+    mut q_highlight: Query<(&mut Transform, &mut Visibility), With<TutorialHighlight>>,
+    mut q_panel: Query<&mut Visibility, (With<TutorialPanel>, Without<TutorialHighlight>)>,
+    mut q_option: Query<
+        (&TutorialOption, &mut Visibility),
+        (Without<TutorialPanel>, Without<TutorialHighlight>),
+    >,
+    mut q_text: Query<&mut Text, With<TutorialText>>,
+) {
+    const HIGHLIGHT_OFFSET: Vec3 = Vec3::new(0.0, 1.5, 0.0);
+
+    let step = tutorial.as_deref().and_then(|tutorial| tutorial.current());
+    let highlighted = tutorial
+        .as_deref()
+        .and_then(|tutorial| tutorial.target)
+        .and_then(|entity| q_target.get(entity).ok());
+
+    for (mut transform, mut visibility) in q_highlight.iter_mut() {
+        if let Some(highlighted) = highlighted {
+            transform.translation = highlighted.translation() + HIGHLIGHT_OFFSET;
+            *visibility = Visibility::Inherited;
+        } else {
+            *visibility = Visibility::Hidden;
+        }
+    }
+
+    let target = if step.is_some() {
+        Visibility::Inherited
+    } else {
+        Visibility::Hidden
+    };
+
+    for mut visibility in q_panel.iter_mut() {
+        if *visibility != target {
+            *visibility = target;
+        }
+    }
+
+    let step = if let Some(step) = step {
+        step
+    } else {
+        return;
+    };
+
+    for (option, mut visibility) in q_option.iter_mut() {
+        let target = match option {
+            TutorialOption::Next if step.until != TutorialGoal::Continue => Visibility::Hidden,
+            _ => Visibility::Inherited,
+        };
+
+        if *visibility != target {
+            *visibility = target;
+        }
+    }
+
+    for mut text in q_text.iter_mut() {
+        if text.sections[0].value != step.text {
+            text.sections[0].value = step.text.clone();
+        }
+    }
+}
@@ -1,4 +1,8 @@
 use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Component)]
+pub struct DeckOutButton;
 
 #[derive(Component)]
 pub struct Menu;
@@ -6,6 +10,9 @@ pub struct Menu;
 #[derive(Component)]
 pub struct Button;
 
+#[derive(Component)]
+pub struct MenuButton;
+
 #[derive(Resource)]
 pub struct MenuMaterials {
     pub button_material: Handle<StandardMaterial>,
@@ -13,12 +20,55 @@ pub struct MenuMaterials {
     pub button_material_hovered: Handle<StandardMaterial>,
 }
 
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq, Serialize)]
+pub enum DeckOutRule {
+    Fatigue,
+    Reshuffle,
+}
+
+impl DeckOutRule {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Fatigue => "fatigue",
+            Self::Reshuffle => "reshuffle graveyard",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Resource)]
 pub struct GameConfig {
+    pub daily: Option<u64>,
+    pub deck_out: DeckOutRule,
     pub deck_size: u32,
+    pub max_turns: Option<u32>,
     pub opponent_hp: u32,
+    pub seed: u64,
+    pub selection: MenuSelection,
 }
 
-#[derive(Clone, Copy, Component)]
+impl GameConfig {
+    pub fn with_daily(mut self, day: u64) -> Self {
+        self.daily = Some(day);
+        self
+    }
+
+    pub fn with_deck_out(mut self, deck_out: DeckOutRule) -> Self {
+        self.deck_out = deck_out;
+        self
+    }
+
+    pub fn with_max_turns(mut self, max_turns: Option<u32>) -> Self {
+        self.max_turns = max_turns;
+        self
+    }
+
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+}
+
+#[derive(Clone, Copy, Component, PartialEq, Eq)]
 pub enum MenuSelection {
     Small,
     Medium,
@@ -26,22 +76,49 @@ pub enum MenuSelection {
 }
 
 impl MenuSelection {
+    pub fn all() -> [Self; 3] {
+        [Self::Small, Self::Medium, Self::Large]
+    }
+
     pub fn game_config(&self) -> GameConfig {
         match self {
             MenuSelection::Small => GameConfig {
+                daily: None,
+                deck_out: DeckOutRule::Fatigue,
                 deck_size: 12,
+                max_turns: None,
                 opponent_hp: 10,
+                seed: 0,
+                selection: *self,
             },
             MenuSelection::Medium => GameConfig {
+                daily: None,
+                deck_out: DeckOutRule::Fatigue,
                 deck_size: 24,
+                max_turns: None,
                 opponent_hp: 20,
+                seed: 0,
+                selection: *self,
             },
             MenuSelection::Large => GameConfig {
+                daily: None,
+                deck_out: DeckOutRule::Fatigue,
                 deck_size: 36,
+                max_turns: None,
                 opponent_hp: 30,
+                seed: 0,
+                selection: *self,
             },
         }
     }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            MenuSelection::Small => "Small",
+            MenuSelection::Medium => "Medium",
+            MenuSelection::Large => "Large",
+        }
+    }
 }
 
 #[derive(Component)]
@@ -50,8 +127,9 @@ pub struct PlayButton;
 #[derive(Component)]
 pub struct ActiveSelection;
 
-#[derive(Component)]
+#[derive(Component, PartialEq, Eq)]
 pub enum GameOverText {
+    Draw,
     Win,
     Lose,
 }
@@ -14,8 +14,14 @@ pub struct CleanUp;
 
 #[derive(PartialEq, Eq)]
 pub enum AttackedEvent {
-    Player(u32),
-    Opponent(u32),
+    Player(u32, DamageSource),
+    Opponent(u32, DamageSource),
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum DamageSource {
+    Attack,
+    Fatigue,
 }
 
 #[derive(Component)]
@@ -0,0 +1,294 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+use crate::{
+    achievements::Achievement,
+    cards::{CardType, CardUnlock},
+    menu::MenuSelection,
+    storage,
+};
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(default)]
+pub struct GameRecord {
+    pub played: u32,
+    pub won: u32,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    Draw,
+    Lose,
+    Win,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Resource, Serialize)]
+#[serde(default)]
+pub struct Profile {
+    pub achievements: Vec<String>,
+    pub best_streak: u32,
+    pub card_usage: BTreeMap<String, u32>,
+    pub current_streak: u32,
+    pub fastest_win: Option<u32>,
+    pub id: String,
+    pub records: BTreeMap<String, GameRecord>,
+    pub unlocked_cards: Vec<String>,
+}
+
+impl Profile {
+    pub fn card_pool(&self) -> Vec<CardType> {
+        CardType::all()
+            .into_iter()
+            .filter(|card_type| {
+                card_type.unlock() == CardUnlock::Starter
+                    || self
+                        .unlocked_cards
+                        .iter()
+                        .any(|name| name == card_type.name())
+            })
+            .collect()
+    }
+
+    pub fn load() -> Self {
+        let mut profile: Self = storage::load("profile");
+
+        // The id tells this install's daily results apart from imported ones.
+        if profile.id.is_empty() {
+            profile.id = format!("{:016x}", rand::random::<u64>());
+            profile.save();
+        }
+
+        profile
+    }
+
+    pub fn record_game(
+        &mut self,
+        selection: MenuSelection,
+        outcome: Outcome,
+        turns: u32,
+        cards_played: &[(CardType, u32)],
+    ) {
+        let record = self
+            .records
+            .entry(selection.name().to_string())
+            .or_default();
+
+        record.played += 1;
+
+        if outcome == Outcome::Win {
+            record.won += 1;
+            self.current_streak += 1;
+            self.best_streak = self.best_streak.max(self.current_streak);
+            self.fastest_win = Some(self.fastest_win.map_or(turns, |fastest| fastest.min(turns)));
+        } else {
+            self.current_streak = 0;
+        }
+
+        for (card_type, count) in cards_played {
+            *self
+                .card_usage
+                .entry(card_type.name().to_string())
+                .or_default() += count;
+        }
+    }
+
+    pub fn save(&self) {
+        storage::save("profile", self);
+    }
+
+    pub fn summary(&self) -> String {
+        let mut lines = vec!["Profile".to_string()];
+
+        for selection in MenuSelection::all() {
+            let record = self
+                .records
+                .get(selection.name())
+                .copied()
+                .unwrap_or_default();
+
+            lines.push(format!(
+                "{}: {} won of {} played",
+                selection.name(),
+                record.won,
+                record.played
+            ));
+        }
+
+        lines.push(format!(
+            "Win streak: {} (best {})",
+            self.current_streak, self.best_streak
+        ));
+        lines.push(format!(
+            "Fastest win: {}",
+            self.fastest_win
+                .map_or("none yet".to_string(), |turns| format!("{turns} turns"))
+        ));
+
+        let usage = self
+            .card_usage
+            .iter()
+            .map(|(name, count)| format!("{name} x{count}"))
+            .collect::<Vec<_>>();
+
+        lines.push(format!(
+            "Cards played: {}",
+            if usage.is_empty() {
+                "none".to_string()
+            } else {
+                usage.join(", ")
+            }
+        ));
+
+        let locked = CardType::all()
+            .into_iter()
+            .filter(|card_type| !self.card_pool().contains(card_type))
+            .map(|card_type| {
+                format!(
+                    "{} ({})",
+                    card_type.name(),
+                    card_type.unlock().description()
+                )
+            })
+            .collect::<Vec<_>>();
+
+        lines.push(format!(
+            "Card pool: {}",
+            self.card_pool()
+                .iter()
+                .map(CardType::name)
+                .collect::<Vec<_>>()
+                .join(", ")
+        ));
+
+        if !locked.is_empty() {
+            lines.push(format!("Locked: {}", locked.join(", ")));
+        }
+
+        let achievements = self
+            .achievements
+            .iter()
+            .filter_map(|id| Achievement::find(id))
+            .map(|achievement| achievement.name)
+            .collect::<Vec<_>>();
+
+        lines.push(format!(
+            "Achievements: {}",
+            if achievements.is_empty() {
+                "none".to_string()
+            } else {
+                achievements.join(", ")
+            }
+        ));
+
+        lines.join("\n")
+    }
+
+    pub fn unlock(&mut self, achievement: &Achievement) -> bool {
+        if self.achievements.iter().any(|id| id == achievement.id) {
+            return false;
+        }
+
+        self.achievements.push(achievement.id.to_string());
+
+        true
+    }
+
+    pub fn unlock_cards(&mut self) -> Vec<CardType> {
+        let unlocked = CardType::all()
+            .into_iter()
+            .filter(|card_type| !self.card_pool().contains(card_type))
+            .filter(|card_type| match card_type.unlock() {
+                CardUnlock::Achievements(count) => self.achievements.len() as u32 >= count,
+                CardUnlock::Starter => true,
+            })
+            .collect::<Vec<_>>();
+
+        for card_type in unlocked.iter() {
+            self.unlocked_cards.push(card_type.name().to_string());
+        }
+
+        unlocked
+    }
+}
+
+#[derive(Component)]
+pub struct ProfileBack;
+
+#[derive(Component)]
+pub struct ProfileButton;
+
+#[derive(Component)]
+pub struct ProfileScreen;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn streaks_and_fastest_wins_are_tracked() {
+        let mut profile = Profile::default();
+
+        profile.record_game(
+            MenuSelection::Small,
+            Outcome::Win,
+            9,
+            &[(CardType::Sword, 2)],
+        );
+        profile.record_game(MenuSelection::Small, Outcome::Win, 7, &[]);
+        profile.record_game(
+            MenuSelection::Large,
+            Outcome::Lose,
+            20,
+            &[(CardType::Sword, 1)],
+        );
+
+        assert_eq!(profile.current_streak, 0);
+        assert_eq!(profile.best_streak, 2);
+        assert_eq!(profile.fastest_win, Some(7));
+        assert_eq!(profile.records["Small"], GameRecord { played: 2, won: 2 });
+        assert_eq!(profile.records["Large"], GameRecord { played: 1, won: 0 });
+        assert_eq!(profile.card_usage["Sword"], 3);
+    }
+
+    #[test]
+    fn profiles_round_trip_through_ron() {
+        let mut profile = Profile::default();
+
+        profile.record_game(
+            MenuSelection::Medium,
+            Outcome::Draw,
+            25,
+            &[(CardType::Heart, 4)],
+        );
+
+        let contents = ron::to_string(&profile).unwrap();
+
+        assert_eq!(ron::from_str::<Profile>(&contents).unwrap(), profile);
+    }
+
+    #[test]
+    fn new_cards_unlock_through_achievements() {
+        let mut profile = Profile::default();
+
+        assert_eq!(
+            profile.card_pool(),
+            vec![
+                CardType::Heart,
+                CardType::Pitchfork,
+                CardType::Sword,
+                CardType::Tower
+            ]
+        );
+        assert!(profile.unlock_cards().is_empty());
+
+        profile.record_game(MenuSelection::Small, Outcome::Win, 9, &[]);
+
+        assert!(profile.unlock_cards().is_empty());
+
+        profile.unlock(Achievement::find("flawless").unwrap());
+
+        assert_eq!(profile.unlock_cards(), vec![CardType::Phoenix]);
+        assert_eq!(profile.card_pool(), CardType::all().to_vec());
+    }
+}
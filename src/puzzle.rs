@@ -0,0 +1,180 @@
+use bevy::prelude::*;
+use serde::Deserialize;
+
+use crate::{
+    cards::{CardType, ABILITY_MAX},
+    deck::{OpponentState, PlayableState, PlayerState},
+    menu::{GameConfig, MenuSelection},
+};
+
+// Generated by build.rs from every file in assets/puzzles.
+include!(concat!(env!("OUT_DIR"), "/puzzles.rs"));
+
+#[derive(Resource)]
+pub struct ActivePuzzle(pub Puzzle);
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct Puzzle {
+    pub description: String,
+    pub goal: PuzzleGoal,
+    pub hand: Vec<CardType>,
+    pub name: String,
+    pub opponent_board: [Option<PuzzleCard>; 4],
+    pub opponent_health: i32,
+    pub player_board: [Option<PuzzleCard>; 4],
+    #[serde(default = "default_player_health")]
+    pub player_health: i32,
+    pub power: u32,
+}
+
+impl Puzzle {
+    pub fn bundled() -> Vec<Self> {
+        PUZZLES
+            .iter()
+            .filter_map(|(file, contents)| match Self::parse(contents) {
+                Ok(puzzle) => Some(puzzle),
+                Err(err) => {
+                    warn!("Ignoring invalid puzzle {file}: {err}");
+                    None
+                }
+            })
+            .collect()
+    }
+
+    pub fn game_config(&self) -> GameConfig {
+        let mut config = MenuSelection::Small.game_config();
+
+        config.deck_size = 0;
+        config.opponent_hp = self.opponent_health.max(0) as u32;
+        config
+    }
+
+    // Stats outside what a card can hold would otherwise be capped silently when spawned.
+    pub fn parse(contents: &str) -> Result<Self, String> {
+        let puzzle = ron::from_str::<Self>(contents).map_err(|err| err.to_string())?;
+
+        for card in puzzle
+            .player_board
+            .iter()
+            .chain(puzzle.opponent_board.iter())
+            .flatten()
+        {
+            if !(0..=ABILITY_MAX).contains(&card.attack)
+                || !(1..=ABILITY_MAX).contains(&card.health)
+            {
+                return Err(format!(
+                    "{:?} with {} attack and {} health is out of range",
+                    card.card_type, card.attack, card.health
+                ));
+            }
+        }
+
+        Ok(puzzle)
+    }
+
+    pub fn opponent_state(&self) -> OpponentState {
+        OpponentState::default()
+            .with_deck_size(0, 0, &[])
+            .with_health(self.opponent_health)
+    }
+
+    pub fn player_state(&self) -> PlayerState {
+        let mut player_state = PlayerState::default()
+            .with_deck_size(0, 0, &[])
+            .with_health(self.player_health);
+
+        // Power goes up by one when the turn starts.
+        player_state.max_power = self.power;
+        player_state.power = self.power.saturating_sub(1);
+        player_state
+    }
+}
+
+#[derive(Component)]
+pub struct PuzzleButton;
+
+#[derive(Clone, Copy, Debug, Deserialize)]
+pub struct PuzzleCard {
+    pub attack: i32,
+    pub card_type: CardType,
+    pub health: i32,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq)]
+pub enum PuzzleGoal {
+    WinThisTurn,
+}
+
+impl PuzzleGoal {
+    pub fn description(&self) -> &'static str {
+        match self {
+            Self::WinThisTurn => "Win this turn",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Component)]
+pub enum PuzzleOption {
+    Back,
+    Play(usize),
+}
+
+#[derive(Component)]
+pub struct PuzzleScreen;
+
+fn default_player_health() -> i32 {
+    10
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bundled_puzzles_are_valid() {
+        let puzzles = Puzzle::bundled();
+
+        assert_eq!(puzzles.len(), PUZZLES.len());
+
+        for puzzle in puzzles {
+            let cost = puzzle
+                .hand
+                .iter()
+                .map(|card_type| card_type.attributes().cost)
+                .min()
+                .unwrap();
+
+            assert!(cost <= puzzle.power, "{} has no playable card", puzzle.name);
+            assert!(puzzle.hand.len() <= 5, "{} overflows the hand", puzzle.name);
+        }
+    }
+
+    #[test]
+    fn out_of_range_stats_are_rejected() {
+        let puzzle = |attack: i32, health: i32| {
+            format!(
+                "(
+                    name: \"Test\",
+                    description: \"\",
+                    goal: WinThisTurn,
+                    hand: [Pitchfork],
+                    power: 1,
+                    player_board: [
+                        Some((card_type: Sword, attack: {attack}, health: {health})),
+                        None,
+                        None,
+                        None,
+                    ],
+                    opponent_board: [None, None, None, None],
+                    opponent_health: 1,
+                )"
+            )
+        };
+
+        assert!(Puzzle::parse(&puzzle(2, 2)).is_ok());
+        assert!(Puzzle::parse(&puzzle(ABILITY_MAX + 1, 2)).is_err());
+        assert!(Puzzle::parse(&puzzle(-1, 2)).is_err());
+        assert!(Puzzle::parse(&puzzle(2, 0)).is_err());
+        assert!(Puzzle::parse(&puzzle(2, ABILITY_MAX + 1)).is_err());
+    }
+}
@@ -0,0 +1,465 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+use crate::{input::InputAction, menu::DeckOutRule, storage};
+
+const ANIMATION_SPEEDS: [f32; 4] = [0.5, 1.0, 2.0, 4.0];
+const CAMERA_DISTANCES: [f32; 3] = [12.0, 15.0, 18.0];
+const CAMERA_HEIGHTS: [f32; 3] = [7.0, 9.0, 11.0];
+const DEFAULT_PLAYER_NAME: &str = "Player";
+const MAX_NAME_LENGTH: usize = 16;
+const TURN_LIMITS: [Option<u32>; 4] = [None, Some(15), Some(25), Some(40)];
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(default)]
+pub struct Bindings {
+    pub buttons: Vec<(GamepadButtonType, InputAction)>,
+    pub ctrl_keys: Vec<(KeyCode, InputAction)>,
+    pub keys: Vec<(KeyCode, InputAction)>,
+    pub mouse_buttons: Vec<(MouseButton, InputAction)>,
+}
+
+impl Default for Bindings {
+    fn default() -> Self {
+        let hand_keys = [
+            KeyCode::Key1,
+            KeyCode::Key2,
+            KeyCode::Key3,
+            KeyCode::Key4,
+            KeyCode::Key5,
+            KeyCode::Key6,
+            KeyCode::Key7,
+            KeyCode::Key8,
+            KeyCode::Key9,
+        ];
+        let mut keys = vec![
+            (KeyCode::Back, InputAction::Undo),
+            (KeyCode::Escape, InputAction::Cancel),
+            (KeyCode::F1, InputAction::Settings),
+            (KeyCode::Left, InputAction::Left),
+            (KeyCode::Return, InputAction::Confirm),
+            (KeyCode::Right, InputAction::Right),
+            (KeyCode::Space, InputAction::Confirm),
+        ];
+
+        keys.extend(
+            hand_keys
+                .into_iter()
+                .zip(0..)
+                .map(|(key, index)| (key, InputAction::Hand(index))),
+        );
+
+        Self {
+            buttons: vec![
+                (GamepadButtonType::DPadLeft, InputAction::Left),
+                (GamepadButtonType::DPadRight, InputAction::Right),
+                (GamepadButtonType::East, InputAction::Cancel),
+                (GamepadButtonType::LeftTrigger, InputAction::PreviousHand),
+                (GamepadButtonType::RightTrigger, InputAction::NextHand),
+                (GamepadButtonType::Select, InputAction::Settings),
+                (GamepadButtonType::South, InputAction::Select),
+                (GamepadButtonType::Start, InputAction::Confirm),
+                (GamepadButtonType::West, InputAction::Undo),
+            ],
+            ctrl_keys: vec![(KeyCode::Z, InputAction::Undo)],
+            keys,
+            mouse_buttons: vec![(MouseButton::Right, InputAction::Undo)],
+        }
+    }
+}
+
+impl Bindings {
+    pub fn button_action(&self, button: GamepadButtonType) -> Option<InputAction> {
+        self.buttons
+            .iter()
+            .find(|(bound, _)| *bound == button)
+            .map(|(_, action)| *action)
+    }
+
+    pub fn ctrl_key_action(&self, key: KeyCode) -> Option<InputAction> {
+        self.ctrl_keys
+            .iter()
+            .find(|(bound, _)| *bound == key)
+            .map(|(_, action)| *action)
+    }
+
+    pub fn describe(&self, action: InputAction) -> String {
+        let keys = self
+            .keys
+            .iter()
+            .filter(|(_, bound)| *bound == action)
+            .map(|(key, _)| format!("{key:?}"))
+            .chain(
+                self.ctrl_keys
+                    .iter()
+                    .filter(|(_, bound)| *bound == action)
+                    .map(|(key, _)| format!("Ctrl+{key:?}")),
+            )
+            .chain(
+                self.mouse_buttons
+                    .iter()
+                    .filter(|(_, bound)| *bound == action)
+                    .map(|(button, _)| format!("Mouse {button:?}")),
+            )
+            .collect::<Vec<_>>();
+        let buttons = self
+            .buttons
+            .iter()
+            .filter(|(_, bound)| *bound == action)
+            .map(|(button, _)| format!("{button:?}"))
+            .collect::<Vec<_>>();
+
+        format!("{} / {}", keys.join(", "), buttons.join(", "))
+    }
+
+    pub fn key_action(&self, key: KeyCode) -> Option<InputAction> {
+        self.keys
+            .iter()
+            .find(|(bound, _)| *bound == key)
+            .map(|(_, action)| *action)
+    }
+
+    pub fn mouse_action(&self, button: MouseButton) -> Option<InputAction> {
+        self.mouse_buttons
+            .iter()
+            .find(|(bound, _)| *bound == button)
+            .map(|(_, action)| *action)
+    }
+
+    pub fn rebind_button(&mut self, action: InputAction, button: GamepadButtonType) {
+        self.buttons
+            .retain(|(bound, bound_action)| *bound != button && *bound_action != action);
+        self.buttons.push((button, action));
+    }
+
+    // A new key replaces every keyboard and mouse binding for the action.
+    pub fn rebind_key(&mut self, action: InputAction, key: KeyCode) {
+        self.keys
+            .retain(|(bound, bound_action)| *bound != key && *bound_action != action);
+        self.ctrl_keys
+            .retain(|(_, bound_action)| *bound_action != action);
+        self.mouse_buttons
+            .retain(|(_, bound_action)| *bound_action != action);
+        self.keys.push((key, action));
+    }
+}
+
+#[derive(Default, Resource)]
+pub struct EditingName(pub bool);
+
+#[derive(Default, Resource)]
+pub struct Rebinding(pub Option<InputAction>);
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Resource, Serialize)]
+#[serde(default)]
+pub struct Settings {
+    pub animation_speed: f32,
+    pub bindings: Bindings,
+    pub camera_distance: f32,
+    pub camera_height: f32,
+    pub deck_out: DeckOutRule,
+    pub max_turns: Option<u32>,
+    pub player_name: String,
+    pub skip_animations: bool,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            animation_speed: 1.0,
+            bindings: Bindings::default(),
+            camera_distance: 15.0,
+            camera_height: 9.0,
+            deck_out: DeckOutRule::Fatigue,
+            max_turns: None,
+            player_name: DEFAULT_PLAYER_NAME.to_string(),
+            skip_animations: false,
+        }
+    }
+}
+
+impl Settings {
+    pub fn animation_duration(&self, millis: u64) -> Option<Duration> {
+        if self.skip_animations {
+            None
+        } else {
+            Some(Duration::from_millis(millis).div_f32(self.animation_speed.max(0.1)))
+        }
+    }
+
+    pub fn camera_offset(&self) -> Vec3 {
+        Vec3::new(0.0, self.camera_height, self.camera_distance)
+    }
+
+    pub fn erase_name(&mut self) {
+        self.player_name.pop();
+    }
+
+    // An empty name can't be told apart on the leaderboard, so it falls back to the default.
+    pub fn finish_name(&mut self) {
+        let name = self.player_name.trim();
+
+        self.player_name = if name.is_empty() {
+            DEFAULT_PLAYER_NAME.to_string()
+        } else {
+            name.to_string()
+        };
+    }
+
+    pub fn type_name(&mut self, character: char) {
+        if !character.is_control() && self.player_name.chars().count() < MAX_NAME_LENGTH {
+            self.player_name.push(character);
+        }
+    }
+
+    pub fn cycle(&mut self, option: SettingsOption) {
+        match option {
+            SettingsOption::AnimationSpeed => {
+                self.animation_speed = next_option(&ANIMATION_SPEEDS, self.animation_speed);
+            }
+            SettingsOption::CameraDistance => {
+                self.camera_distance = next_option(&CAMERA_DISTANCES, self.camera_distance);
+            }
+            SettingsOption::CameraHeight => {
+                self.camera_height = next_option(&CAMERA_HEIGHTS, self.camera_height);
+            }
+            SettingsOption::DeckOut => {
+                self.deck_out = match self.deck_out {
+                    DeckOutRule::Fatigue => DeckOutRule::Reshuffle,
+                    DeckOutRule::Reshuffle => DeckOutRule::Fatigue,
+                };
+            }
+            SettingsOption::SkipAnimations => {
+                self.skip_animations = !self.skip_animations;
+            }
+            SettingsOption::TurnLimit => {
+                let index = TURN_LIMITS
+                    .iter()
+                    .position(|limit| *limit == self.max_turns)
+                    .map_or(0, |index| (index + 1) % TURN_LIMITS.len());
+
+                self.max_turns = TURN_LIMITS[index];
+            }
+            SettingsOption::Back | SettingsOption::Binding(_) | SettingsOption::PlayerName => {}
+        }
+    }
+
+    pub fn load() -> Self {
+        storage::load("settings")
+    }
+
+    pub fn save(&self) {
+        storage::save("settings", self);
+    }
+}
+
+#[derive(Clone, Copy, Component)]
+pub enum SettingsOption {
+    AnimationSpeed,
+    Back,
+    Binding(InputAction),
+    CameraDistance,
+    CameraHeight,
+    DeckOut,
+    PlayerName,
+    SkipAnimations,
+    TurnLimit,
+}
+
+impl SettingsOption {
+    pub fn all() -> Vec<Self> {
+        let mut options = vec![
+            Self::PlayerName,
+            Self::AnimationSpeed,
+            Self::SkipAnimations,
+            Self::CameraHeight,
+            Self::CameraDistance,
+            Self::DeckOut,
+            Self::TurnLimit,
+        ];
+
+        options.extend(InputAction::all().into_iter().map(Self::Binding));
+        options.push(Self::Back);
+
+        options
+    }
+
+    pub fn label(
+        &self,
+        settings: &Settings,
+        rebinding: Option<InputAction>,
+        editing_name: bool,
+    ) -> String {
+        match self {
+            Self::AnimationSpeed => format!("Animation speed: {}x", settings.animation_speed),
+            Self::Back => "Save and return".to_string(),
+            Self::Binding(action) if rebinding == Some(*action) => {
+                format!("{}: press a key or button...", action.label())
+            }
+            Self::Binding(action) => format!(
+                "{}: {}",
+                action.label(),
+                settings.bindings.describe(*action)
+            ),
+            Self::CameraDistance => format!("Camera distance: {}", settings.camera_distance),
+            Self::CameraHeight => format!("Camera height: {}", settings.camera_height),
+            Self::DeckOut => format!("Empty deck: {}", settings.deck_out.name()),
+            Self::PlayerName if editing_name => {
+                format!("Name: {}_ (enter to finish)", settings.player_name)
+            }
+            Self::PlayerName => format!("Name: {}", settings.player_name),
+            Self::SkipAnimations if settings.skip_animations => "Skip animations: on".to_string(),
+            Self::SkipAnimations => "Skip animations: off".to_string(),
+            Self::TurnLimit => match settings.max_turns {
+                Some(max_turns) => format!("Turn limit: {max_turns}"),
+                None => "Turn limit: off".to_string(),
+            },
+        }
+    }
+}
+
+#[derive(Component)]
+pub struct SettingsButton;
+
+#[derive(Component)]
+pub struct SettingsMenu;
+
+fn next_option(options: &[f32], current: f32) -> f32 {
+    options
+        .iter()
+        .copied()
+        .find(|option| *option > current)
+        .unwrap_or(options[0])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn animation_durations_follow_speed_and_skip() {
+        let mut settings = Settings::default();
+
+        assert_eq!(
+            settings.animation_duration(500),
+            Some(Duration::from_millis(500))
+        );
+
+        settings.animation_speed = 2.0;
+
+        assert_eq!(
+            settings.animation_duration(500),
+            Some(Duration::from_millis(250))
+        );
+
+        settings.cycle(SettingsOption::SkipAnimations);
+
+        assert_eq!(settings.animation_duration(500), None);
+    }
+
+    #[test]
+    fn deck_out_rule_cycles_between_fatigue_and_reshuffle() {
+        let mut settings = Settings::default();
+
+        assert_eq!(settings.deck_out, DeckOutRule::Fatigue);
+
+        settings.cycle(SettingsOption::DeckOut);
+
+        assert_eq!(settings.deck_out, DeckOutRule::Reshuffle);
+        assert_eq!(
+            SettingsOption::DeckOut.label(&settings, None, false),
+            "Empty deck: reshuffle graveyard"
+        );
+
+        settings.cycle(SettingsOption::DeckOut);
+
+        assert_eq!(settings.deck_out, DeckOutRule::Fatigue);
+    }
+
+    #[test]
+    fn turn_limit_cycles_back_to_off() {
+        let mut settings = Settings::default();
+        let mut limits = Vec::new();
+
+        for _ in 0..TURN_LIMITS.len() {
+            settings.cycle(SettingsOption::TurnLimit);
+            limits.push(SettingsOption::TurnLimit.label(&settings, None, false));
+        }
+
+        assert_eq!(
+            limits,
+            [
+                "Turn limit: 15",
+                "Turn limit: 25",
+                "Turn limit: 40",
+                "Turn limit: off"
+            ]
+        );
+    }
+
+    #[test]
+    fn player_name_is_typed_and_never_left_empty() {
+        let mut settings = Settings::default();
+
+        for _ in 0..DEFAULT_PLAYER_NAME.len() {
+            settings.erase_name();
+        }
+
+        "Ana\r Lovelace-Byron the Great"
+            .chars()
+            .for_each(|character| settings.type_name(character));
+
+        assert_eq!(settings.player_name, "Ana Lovelace-Byr");
+        assert_eq!(
+            SettingsOption::PlayerName.label(&settings, None, true),
+            "Name: Ana Lovelace-Byr_ (enter to finish)"
+        );
+
+        settings.player_name = "  ".to_string();
+        settings.finish_name();
+
+        assert_eq!(settings.player_name, DEFAULT_PLAYER_NAME);
+    }
+
+    #[test]
+    fn rebinding_a_key_replaces_both_sides() {
+        let mut bindings = Bindings::default();
+
+        assert_eq!(
+            bindings.describe(InputAction::Undo),
+            "Back, Ctrl+Z, Mouse Right / West"
+        );
+
+        bindings.rebind_key(InputAction::Undo, KeyCode::Space);
+
+        assert_eq!(bindings.key_action(KeyCode::Space), Some(InputAction::Undo));
+        assert_eq!(bindings.key_action(KeyCode::Back), None);
+        assert_eq!(bindings.ctrl_key_action(KeyCode::Z), None);
+        assert_eq!(bindings.mouse_action(MouseButton::Right), None);
+        assert_eq!(
+            bindings.key_action(KeyCode::Return),
+            Some(InputAction::Confirm)
+        );
+    }
+
+    #[test]
+    fn settings_round_trip_through_ron() {
+        let mut settings = Settings::default();
+
+        settings.cycle(SettingsOption::AnimationSpeed);
+        settings
+            .bindings
+            .rebind_button(InputAction::Confirm, GamepadButtonType::North);
+
+        let contents = ron::to_string(&settings).unwrap();
+
+        assert_eq!(ron::from_str::<Settings>(&contents).unwrap(), settings);
+        assert_eq!(
+            ron::from_str::<Settings>("(animation_speed: 4.0)")
+                .unwrap()
+                .bindings,
+            Bindings::default()
+        );
+    }
+}
@@ -1,10 +1,16 @@
 use bevy::prelude::States;
+use serde::Deserialize;
 
-#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash, States)]
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Hash, States)]
 pub enum GameState {
     #[default]
+    Loading,
     Setup,
+    Profile,
+    Settings,
+    Puzzles,
     StartGame,
+    Mulligan,
     PlayerTurn,
     PlayerAttacking,
     OpponentPlayCards,
@@ -12,13 +18,34 @@ pub enum GameState {
     OpponentAttacking,
     Win,
     Lose,
+    Draw,
 }
 
 impl GameState {
+    pub fn banner(&self) -> Option<&'static str> {
+        match self {
+            Self::Mulligan => Some("Choose cards to redraw"),
+            Self::PlayerTurn => Some("Your turn"),
+            Self::PlayerAttacking => Some("Your cards attack"),
+            Self::OpponentPlayCards => Some("Opponent's turn"),
+            Self::OpponentAttacking => Some("Opponent attacks"),
+            _ => None,
+        }
+    }
+
+    pub fn is_overlay(&self) -> bool {
+        matches!(self, Self::Profile | Self::Puzzles | Self::Settings)
+    }
+
     pub fn next(&self) -> Option<Self> {
         match self {
+            Self::Loading => Some(Self::Setup),
             Self::Setup => Some(Self::StartGame),
-            Self::StartGame => Some(Self::PlayerTurn),
+            Self::Profile => None,
+            Self::Settings => None,
+            Self::Puzzles => None,
+            Self::StartGame => Some(Self::Mulligan),
+            Self::Mulligan => Some(Self::PlayerTurn),
             Self::PlayerTurn => Some(Self::PlayerAttacking),
             Self::PlayerAttacking => Some(Self::OpponentPlayCards),
             Self::OpponentPlayCards => Some(Self::OpponentTurn),
@@ -26,6 +53,7 @@ impl GameState {
             Self::OpponentAttacking => Some(Self::PlayerTurn),
             Self::Win => None,
             Self::Lose => None,
+            Self::Draw => None,
         }
     }
 }
@@ -0,0 +1,139 @@
+use bevy::prelude::*;
+
+use crate::cards::CardType;
+
+pub struct CardHitEvent {
+    pub attacker: Entity,
+    pub damage: u32,
+}
+
+#[derive(Default, Resource)]
+pub struct MatchStats {
+    pub biggest_hit: u32,
+    pub cards_destroyed: u32,
+    pub cards_lost: u32,
+    cards_played: Vec<(CardType, u32)>,
+    pub damage_dealt: u32,
+    pub damage_taken: u32,
+    hits: Vec<(Entity, CardType, u32)>,
+}
+
+impl MatchStats {
+    pub fn cards_played(&self) -> &[(CardType, u32)] {
+        &self.cards_played
+    }
+
+    pub fn mvp(&self) -> Option<(CardType, u32)> {
+        self.hits
+            .iter()
+            .max_by_key(|(_, _, damage)| *damage)
+            .map(|(_, card_type, damage)| (*card_type, *damage))
+    }
+
+    pub fn record_hit(&mut self, attacker: Entity, card_type: CardType, damage: u32) {
+        self.biggest_hit = self.biggest_hit.max(damage);
+
+        if let Some(hit) = self
+            .hits
+            .iter_mut()
+            .find(|(entity, _, _)| *entity == attacker)
+        {
+            hit.2 += damage;
+        } else {
+            self.hits.push((attacker, card_type, damage));
+        }
+    }
+
+    pub fn record_play(&mut self, card_type: CardType) {
+        if let Some(played) = self
+            .cards_played
+            .iter_mut()
+            .find(|(played, _)| *played == card_type)
+        {
+            played.1 += 1;
+        } else {
+            self.cards_played.push((card_type, 1));
+        }
+    }
+
+    pub fn summary(&self, turns: u32, seed: u64) -> String {
+        let cards_played = if self.cards_played.is_empty() {
+            "none".to_string()
+        } else {
+            self.cards_played
+                .iter()
+                .map(|(card_type, count)| format!("{} x{count}", card_type.name()))
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        let mvp = self
+            .mvp()
+            .map_or("none".to_string(), |(card_type, damage)| {
+                format!("{} ({damage} damage)", card_type.name())
+            });
+
+        [
+            format!("Turns played: {turns}"),
+            format!("Damage dealt: {}", self.damage_dealt),
+            format!("Damage taken: {}", self.damage_taken),
+            format!("Cards played: {cards_played}"),
+            format!("Cards lost: {}", self.cards_lost),
+            format!("Cards destroyed: {}", self.cards_destroyed),
+            format!("Biggest hit: {}", self.biggest_hit),
+            format!("MVP: {mvp}"),
+            format!("Seed: {seed}"),
+        ]
+        .join("\n")
+    }
+
+    pub fn unrecord_play(&mut self, card_type: CardType) {
+        if let Some(played) = self
+            .cards_played
+            .iter_mut()
+            .find(|(played, _)| *played == card_type)
+        {
+            played.1 = played.1.saturating_sub(1);
+        }
+
+        self.cards_played.retain(|(_, count)| *count > 0);
+    }
+}
+
+#[derive(Clone, Copy, Component)]
+pub enum ResultsButton {
+    MainMenu,
+    Rematch,
+}
+
+#[derive(Component)]
+pub struct ResultsScreen;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mvp_is_the_card_with_the_most_total_damage() {
+        let mut stats = MatchStats::default();
+        let sword = Entity::from_raw(1);
+        let pitchfork = Entity::from_raw(2);
+
+        stats.record_hit(sword, CardType::Sword, 4);
+        stats.record_hit(pitchfork, CardType::Pitchfork, 3);
+        stats.record_hit(pitchfork, CardType::Pitchfork, 3);
+
+        assert_eq!(stats.biggest_hit, 4);
+        assert_eq!(stats.mvp(), Some((CardType::Pitchfork, 6)));
+    }
+
+    #[test]
+    fn undone_plays_are_not_counted() {
+        let mut stats = MatchStats::default();
+
+        stats.record_play(CardType::Sword);
+        stats.record_play(CardType::Heart);
+        stats.unrecord_play(CardType::Sword);
+
+        assert!(stats.summary(3, 9).contains("Cards played: Heart x1\n"));
+    }
+}
@@ -0,0 +1,124 @@
+use bevy::prelude::*;
+use serde::{de::DeserializeOwned, Serialize};
+
+pub fn export<T: Serialize>(name: &str, value: &T) -> bool {
+    let result = serde_json::to_string_pretty(value)
+        .map_err(|err| err.to_string())
+        .and_then(|contents| download(name, &contents));
+
+    if let Err(err) = &result {
+        warn!("Unable to export {name}: {err}");
+    }
+
+    result.is_ok()
+}
+
+pub fn import<T: DeserializeOwned>(name: &str) -> Option<T> {
+    let contents = upload(name)?;
+
+    match serde_json::from_str(&contents) {
+        Ok(value) => Some(value),
+        Err(err) => {
+            warn!("Ignoring invalid {name}: {err}");
+            None
+        }
+    }
+}
+
+pub fn load<T: DeserializeOwned + Default>(name: &str) -> T {
+    if let Some(contents) = read(name, "ron") {
+        match ron::from_str(&contents) {
+            Ok(value) => return value,
+            Err(err) => warn!("Ignoring invalid {name}: {err}"),
+        }
+    }
+
+    T::default()
+}
+
+pub fn save<T: Serialize>(name: &str, value: &T) {
+    let result = ron::ser::to_string_pretty(value, ron::ser::PrettyConfig::default())
+        .map_err(|err| err.to_string())
+        .and_then(|contents| write(name, "ron", &contents));
+
+    if let Err(err) = result {
+        warn!("Unable to save {name}: {err}");
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn download(name: &str, contents: &str) -> Result<(), String> {
+    write(name, "json", contents)
+}
+
+// Browsers can't write next to the game, so exports are offered as a download.
+#[cfg(target_arch = "wasm32")]
+fn download(name: &str, contents: &str) -> Result<(), String> {
+    use wasm_bindgen::JsCast;
+
+    let document = web_sys::window()
+        .and_then(|window| window.document())
+        .ok_or_else(|| "document is unavailable".to_string())?;
+    let parts = js_sys::Array::of1(&contents.into());
+    let mut options = web_sys::BlobPropertyBag::new();
+
+    options.type_("application/json");
+
+    let blob = web_sys::Blob::new_with_str_sequence_and_options(&parts, &options)
+        .map_err(|err| format!("{err:?}"))?;
+    let url = web_sys::Url::create_object_url_with_blob(&blob).map_err(|err| format!("{err:?}"))?;
+    let anchor = document
+        .create_element("a")
+        .map_err(|err| format!("{err:?}"))?
+        .unchecked_into::<web_sys::HtmlAnchorElement>();
+
+    anchor.set_href(&url);
+    anchor.set_download(&format!("{name}.json"));
+    anchor.click();
+
+    web_sys::Url::revoke_object_url(&url).map_err(|err| format!("{err:?}"))
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn upload(name: &str) -> Option<String> {
+    read(name, "json")
+}
+
+// Browsers can't read files without an async picker, so imports are pasted in.
+#[cfg(target_arch = "wasm32")]
+fn upload(name: &str) -> Option<String> {
+    web_sys::window()?
+        .prompt_with_message(&format!("Paste the contents of {name}.json"))
+        .ok()?
+        .filter(|contents| !contents.trim().is_empty())
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn read(name: &str, extension: &str) -> Option<String> {
+    std::fs::read_to_string(format!("{name}.{extension}")).ok()
+}
+
+#[cfg(target_arch = "wasm32")]
+fn read(name: &str, _extension: &str) -> Option<String> {
+    local_storage()?
+        .get_item(&format!("sigil-siege-{name}"))
+        .ok()?
+}
+
+#[cfg(target_arch = "wasm32")]
+fn local_storage() -> Option<web_sys::Storage> {
+    web_sys::window()?.local_storage().ok()?
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn write(name: &str, extension: &str, contents: &str) -> Result<(), String> {
+    std::fs::write(format!("{name}.{extension}"), contents).map_err(|err| err.to_string())
+}
+
+#[cfg(target_arch = "wasm32")]
+fn write(name: &str, _extension: &str, contents: &str) -> Result<(), String> {
+    local_storage()
+        .ok_or_else(|| "local storage is unavailable".to_string())?
+        .set_item(&format!("sigil-siege-{name}"), contents)
+        .map_err(|err| format!("{err:?}"))
+}
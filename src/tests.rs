@@ -0,0 +1,1143 @@
+use bevy::time::TimeUpdateStrategy;
+use bevy_tweening::component_animator_system;
+use proptest::prelude::*;
+use std::{collections::HashMap, time::Duration};
+
+use super::*;
+
+const MAX_FRAMES: u32 = 200;
+
+#[derive(Clone, Debug)]
+enum Action {
+    Damage(u32, i32),
+    Play(CardType, u32),
+}
+
+struct TestGame {
+    app: App,
+}
+
+impl TestGame {
+    fn new(player_state: PlayerState, opponent_state: OpponentState) -> Self {
+        let mut app = App::new();
+
+        app.add_plugins(MinimalPlugins)
+            .add_plugin(SigilSiegePlugin)
+            .add_event::<PickingEvent>()
+            .add_event::<ReceivedCharacter>()
+            .add_event::<TweenCompleted>()
+            .add_system(component_animator_system::<Transform>)
+            .init_resource::<Input<GamepadButton>>()
+            .init_resource::<Input<KeyCode>>()
+            .init_resource::<Input<MouseButton>>()
+            .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+                100,
+            )))
+            .insert_resource(BoardAssets {
+                block_material: default(),
+                block_mesh: default(),
+            })
+            .insert_resource(CardAssets {
+                black_material: default(),
+                card_material: default(),
+                card_mesh: default(),
+                heart_material: default(),
+                heart_mesh: default(),
+                gem_empty_material: default(),
+                gem_material: default(),
+                gem_mesh: default(),
+                phoenix_material: default(),
+                phoenix_mesh: default(),
+                pitchfork_mesh: default(),
+                sword_mesh: default(),
+                tower_mesh: default(),
+            })
+            .insert_resource(EditingName::default())
+            .insert_resource(CardPlaceholderMaterials {
+                invisable: default(),
+                hovered: default(),
+            })
+            .insert_resource(MenuMaterials {
+                button_material: default(),
+                button_material_active: default(),
+                button_material_hovered: default(),
+            })
+            .insert_resource(Focus::default())
+            .insert_resource(MenuSelection::Small.game_config())
+            .insert_resource(PreviewMaterials {
+                buffed: default(),
+                buffed_by: default(),
+                facing: default(),
+            })
+            .insert_resource(Rebinding::default())
+            .insert_resource(Settings::default())
+            .insert_resource(State(GameState::Setup))
+            .insert_resource(UiAssets { font: default() })
+            .insert_resource(opponent_state)
+            .insert_resource(player_state);
+
+        for index in 0..4 {
+            let x = index as f32 * (CARD_WIDTH + 1.0) - 4.5;
+
+            app.world.spawn((
+                CardPlaceholder(index),
+                Player,
+                Transform::from_xyz(x, 0.0, 2.0),
+                Handle::<StandardMaterial>::default(),
+            ));
+            app.world.spawn((
+                CardPlaceholder(index),
+                Opponent,
+                Transform::from_xyz(x, 0.0, -2.0),
+            ));
+        }
+
+        app.world.spawn((Tooltip::default(), Visibility::Hidden));
+        app.world
+            .spawn((Text::from_section("", TextStyle::default()), TooltipText));
+        app.world.spawn((TurnDial, Transform::default()));
+        app.world.spawn((Menu, Transform::default()));
+        app.world.spawn((Camera::default(), Transform::default()));
+        app.world.spawn((
+            MenuSelection::Small,
+            ActiveSelection,
+            Button,
+            Handle::<StandardMaterial>::default(),
+        ));
+        app.world
+            .spawn((PlayButton, Button, Handle::<StandardMaterial>::default()));
+
+        Self { app }
+    }
+
+    fn click(&mut self, entity: Entity) {
+        self.app.world.send_event(PickingEvent::Clicked(entity));
+        self.app.update();
+    }
+
+    fn placeholder(&mut self, index: u32) -> Entity {
+        self.app
+            .world
+            .query_filtered::<(Entity, &CardPlaceholder), With<Player>>()
+            .iter(&self.app.world)
+            .find(|(_, placeholder)| placeholder.0 == index)
+            .map(|(entity, _)| entity)
+            .unwrap()
+    }
+
+    fn play(&mut self, card: Entity, index: u32) {
+        let placeholder = self.placeholder(index);
+
+        self.click(card);
+        self.click(placeholder);
+        self.app.update();
+    }
+
+    fn press(&mut self, key: KeyCode) {
+        self.app.world.resource_mut::<Input<KeyCode>>().press(key);
+        self.app.update();
+
+        let mut keys = self.app.world.resource_mut::<Input<KeyCode>>();
+
+        keys.release(key);
+        keys.clear();
+        self.app.update();
+    }
+
+    fn run_until(&mut self, condition: impl Fn(&mut World) -> bool) {
+        for _ in 0..MAX_FRAMES {
+            if condition(&mut self.app.world) {
+                return;
+            }
+
+            self.app.update();
+        }
+
+        panic!("condition not met within {MAX_FRAMES} frames");
+    }
+
+    fn single<C: Component>(&mut self) -> Entity {
+        self.app
+            .world
+            .query_filtered::<Entity, With<C>>()
+            .single(&self.app.world)
+    }
+
+    fn start(&mut self) {
+        self.app
+            .world
+            .resource_mut::<NextState<GameState>>()
+            .set(GameState::StartGame);
+        self.run_until(|world| state(world) == GameState::Mulligan);
+        self.run_until(|world| world.query::<&Draw>().iter(world).next().is_none());
+
+        let dial = self.single::<TurnDial>();
+
+        self.click(dial);
+        self.run_until(|world| state(world) == GameState::PlayerTurn);
+    }
+}
+
+fn attack(world: &World, entity: Entity) -> i32 {
+    world.get::<Attack>(entity).unwrap().0
+}
+
+fn hand(world: &mut World) -> Vec<Entity> {
+    let mut hand = world
+        .query::<(Entity, &Hand)>()
+        .iter(world)
+        .map(|(entity, hand)| (entity, hand.0))
+        .collect::<Vec<_>>();
+
+    hand.sort_by_key(|(_, index)| *index);
+    hand.into_iter().map(|(entity, _)| entity).collect()
+}
+
+fn action() -> impl Strategy<Value = Action> {
+    let card_type = prop_oneof![
+        Just(CardType::Heart),
+        Just(CardType::Phoenix),
+        Just(CardType::Pitchfork),
+        Just(CardType::Sword),
+        Just(CardType::Tower),
+    ];
+
+    prop_oneof![
+        (0..4_u32, 1..4_i32).prop_map(|(index, damage)| Action::Damage(index, damage)),
+        (card_type, 0..4_u32).prop_map(|(card_type, index)| Action::Play(card_type, index)),
+    ]
+}
+
+fn state(world: &World) -> GameState {
+    world.resource::<State<GameState>>().0
+}
+
+fn unsettled(world: &mut World) -> bool {
+    world
+        .query_filtered::<(), Or<(With<Damage>, With<Killed>, With<PendingAbility>)>>()
+        .iter(world)
+        .next()
+        .is_some()
+}
+
+#[test]
+fn play_button_deals_opening_hand() {
+    let mut game = TestGame::new(PlayerState::default(), OpponentState::default());
+    let play_button = game.single::<PlayButton>();
+
+    game.click(play_button);
+    game.run_until(|world| {
+        state(world) == GameState::Mulligan && hand(world).len() == OPENING_HAND_SIZE as usize
+    });
+
+    let dial = game.single::<TurnDial>();
+
+    game.click(dial);
+    game.run_until(|world| state(world) == GameState::PlayerTurn);
+
+    for _ in 0..5 {
+        game.app.update();
+    }
+
+    let world = &mut game.app.world;
+
+    assert_eq!(state(world), GameState::PlayerTurn);
+    assert_eq!(hand(world).len(), OPENING_HAND_SIZE as usize);
+    assert_eq!(world.resource::<PlayerState>().available_power, 1);
+}
+
+#[test]
+fn playing_a_card_spends_power() {
+    let mut game = TestGame::new(
+        PlayerState::default().with_cards(vec![CardType::Pitchfork; 6]),
+        OpponentState::default(),
+    );
+
+    game.start();
+
+    let card = hand(&mut game.app.world)[0];
+
+    game.play(card, 0);
+
+    let world = &mut game.app.world;
+    let spent = world
+        .query::<&Power>()
+        .iter(world)
+        .filter(|power| !power.available)
+        .count();
+
+    assert_eq!(world.resource::<PlayerBoard>().all()[0].entity, card);
+    assert_eq!(world.resource::<PlayerState>().available_power, 0);
+    assert_eq!(spent, 1);
+    assert_eq!(hand(world).len(), OPENING_HAND_SIZE as usize - 1);
+}
+
+#[test]
+fn keyboard_can_pick_and_place_a_card() {
+    let mut game = TestGame::new(
+        PlayerState::default().with_cards(vec![CardType::Pitchfork; 6]),
+        OpponentState::default(),
+    );
+
+    game.start();
+
+    let card = hand(&mut game.app.world)[1];
+
+    game.press(KeyCode::Key2);
+    game.press(KeyCode::Right);
+    game.press(KeyCode::Right);
+    game.press(KeyCode::Return);
+
+    let world = &mut game.app.world;
+
+    assert_eq!(world.resource::<PlayerBoard>().all()[0].entity, card);
+    assert_eq!(world.resource::<PlayerState>().available_power, 0);
+    assert!(!world.resource::<PlayerBoard>().unoccupied(1));
+}
+
+#[test]
+fn profile_screen_opens_from_the_menu_and_closes_on_cancel() {
+    let mut game = TestGame::new(PlayerState::default(), OpponentState::default());
+
+    game.app.insert_resource(Profile::default());
+
+    let button = game
+        .app
+        .world
+        .spawn((Interaction::Clicked, Visibility::Hidden, ProfileButton))
+        .id();
+
+    game.app.update();
+    game.app.update();
+    game.app.world.entity_mut(button).insert(Interaction::None);
+
+    let world = &mut game.app.world;
+    let screens = world.query::<&ProfileScreen>().iter(world).count();
+
+    assert_eq!(state(world), GameState::Profile);
+    assert_eq!(screens, 1);
+
+    game.press(KeyCode::Escape);
+    game.app.update();
+
+    let world = &mut game.app.world;
+    let screens = world.query::<&ProfileScreen>().iter(world).count();
+
+    assert_eq!(state(world), GameState::Setup);
+    assert_eq!(screens, 0);
+}
+
+#[test]
+fn settings_screen_blocks_menu_clicks() {
+    let mut game = TestGame::new(PlayerState::default(), OpponentState::default());
+    let play_button = game.single::<PlayButton>();
+
+    game.press(KeyCode::F1);
+    game.click(play_button);
+    game.app.update();
+
+    let world = &mut game.app.world;
+    let options = world.query::<&SettingsOption>().iter(world).count();
+
+    assert_eq!(state(world), GameState::Settings);
+    assert_eq!(options, SettingsOption::all().len());
+}
+
+#[test]
+fn player_name_is_typed_in_the_settings_screen() {
+    let mut game = TestGame::new(PlayerState::default(), OpponentState::default());
+
+    game.press(KeyCode::F1);
+
+    let option = game
+        .app
+        .world
+        .query::<(Entity, &SettingsOption)>()
+        .iter(&game.app.world)
+        .find(|(_, option)| matches!(option, SettingsOption::PlayerName))
+        .map(|(entity, _)| entity)
+        .unwrap();
+    let window = game.app.world.spawn_empty().id();
+
+    game.app
+        .world
+        .entity_mut(option)
+        .insert(Interaction::Clicked);
+    game.app.update();
+
+    for char in "Ana".chars() {
+        game.app
+            .world
+            .send_event(ReceivedCharacter { window, char });
+    }
+
+    game.app.update();
+
+    assert_eq!(
+        game.app.world.get::<Text>(option).unwrap().sections[0].value,
+        "Name: Ana_ (enter to finish)"
+    );
+
+    game.press(KeyCode::Escape);
+
+    let world = &game.app.world;
+
+    assert_eq!(state(world), GameState::Settings);
+    assert_eq!(world.resource::<Settings>().player_name, "Ana");
+    assert_eq!(
+        world.get::<Text>(option).unwrap().sections[0].value,
+        "Name: Ana"
+    );
+}
+
+#[test]
+fn phase_banner_announces_the_player_turn() {
+    let mut game = TestGame::new(PlayerState::default(), OpponentState::default());
+    let banner = game
+        .app
+        .world
+        .spawn((
+            Text::from_section("", TextStyle::default()),
+            Visibility::Hidden,
+            PhaseBanner::default(),
+        ))
+        .id();
+
+    game.start();
+
+    let world = &game.app.world;
+
+    assert_eq!(
+        world.get::<Text>(banner).unwrap().sections[0].value,
+        "Your turn"
+    );
+    assert_eq!(
+        world.get::<Visibility>(banner),
+        Some(&Visibility::Inherited)
+    );
+
+    game.run_until(|world| world.get::<Visibility>(banner) == Some(&Visibility::Hidden));
+}
+
+#[test]
+fn stats_panel_shows_numeric_readouts() {
+    let mut game = TestGame::new(
+        PlayerState::default().with_cards(vec![CardType::Pitchfork; 6]),
+        OpponentState::default(),
+    );
+    let panel = game
+        .app
+        .world
+        .spawn((
+            Text::from_section("", TextStyle::default()),
+            Visibility::Hidden,
+            StatsPanel,
+        ))
+        .id();
+
+    game.start();
+
+    let card = hand(&mut game.app.world)[0];
+
+    game.play(card, 0);
+
+    let world = &game.app.world;
+    let text = &world.get::<Text>(panel).unwrap().sections[0].value;
+
+    assert_eq!(world.get::<Visibility>(panel), Some(&Visibility::Inherited));
+    assert!(text.contains("Power 0/1"), "{text}");
+    assert!(
+        text.contains(&format!("Hand {} /", OPENING_HAND_SIZE - 1)),
+        "{text}"
+    );
+    assert!(text.contains("Tower 12/12"), "{text}");
+}
+
+#[test]
+fn killed_cards_take_their_sigils_with_them() {
+    let mut game = TestGame::new(
+        PlayerState::default().with_cards(vec![CardType::Pitchfork; 6]),
+        OpponentState::default(),
+    );
+
+    game.start();
+    game.app.world.resource_mut::<PlayerState>().available_power = 2;
+
+    let first = hand(&mut game.app.world)[0];
+
+    game.play(first, 0);
+
+    let second = hand(&mut game.app.world)[0];
+
+    game.play(second, 1);
+
+    assert_eq!(attack(&game.app.world, first), 2);
+    assert_eq!(attack(&game.app.world, second), 2);
+
+    game.app.world.entity_mut(first).insert(Killed);
+    game.app.update();
+
+    let world = &mut game.app.world;
+
+    assert_eq!(attack(world, second), 1);
+    assert_eq!(world.resource::<PlayerBoard>().all().len(), 1);
+    assert_eq!(world.resource::<PlayerState>().graveyard().size(), 1);
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(32))]
+
+    #[test]
+    fn stats_are_base_plus_living_auras(actions in prop::collection::vec(action(), 0..20)) {
+        let mut game = TestGame::new(
+            PlayerState::default().with_cards(vec![CardType::Pitchfork; 6]),
+            OpponentState::default(),
+        );
+        let mut wounds = HashMap::new();
+
+        game.app.add_system(apply_damage);
+        game.start();
+
+        for action in actions {
+            let world = &mut game.app.world;
+            let board = world.resource::<PlayerBoard>();
+
+            match action {
+                Action::Damage(index, damage) => {
+                    let entity = match board.all().get(index as usize) {
+                        Some(placement) => placement.entity,
+                        None => continue,
+                    };
+
+                    world.entity_mut(entity).insert(Damage(damage));
+                    *wounds.entry(entity).or_insert(0) += damage;
+                }
+                Action::Play(card_type, index) => {
+                    if !board.unoccupied(index) {
+                        continue;
+                    }
+
+                    let attributes = card_type.attributes();
+                    let hand_size = hand(world).len() as u32;
+                    let card = world
+                        .spawn((
+                            card_type,
+                            Hand(hand_size),
+                            Attack(attributes.attack as i32),
+                            Cost(0),
+                            Health(attributes.health as i32),
+                            Player,
+                            Transform::default(),
+                        ))
+                        .id();
+
+                    game.play(card, index);
+                }
+            }
+
+            game.run_until(|world| !unsettled(world));
+
+            let world = &game.app.world;
+            let board = world.resource::<PlayerBoard>();
+
+            for placement in board.all() {
+                let attributes = placement.card_type.attributes();
+                let (expected_attack, expected_health) = board
+                    .all()
+                    .into_iter()
+                    .filter(|source| {
+                        source
+                            .card_type
+                            .affects(source.entity, board.state())
+                            .contains(&placement.entity)
+                    })
+                    .map(|source| source.card_type.effect())
+                    .fold(
+                        (attributes.attack as i32, attributes.health as i32),
+                        |(attack, health), effect| (attack + effect.attack, health + effect.health),
+                    );
+                let taken = wounds.get(&placement.entity).copied().unwrap_or(0);
+                let current_health = world.get::<Health>(placement.entity).unwrap().0;
+
+                prop_assert_eq!(
+                    attack(world, placement.entity),
+                    expected_attack.min(ABILITY_MAX)
+                );
+                prop_assert_eq!(current_health, expected_health.min(ABILITY_MAX) - taken);
+                prop_assert!(current_health > 0);
+            }
+        }
+    }
+}
+
+#[test]
+fn hovering_a_card_shows_its_buff_sources() {
+    let mut game = TestGame::new(
+        PlayerState::default().with_cards(vec![CardType::Pitchfork; 6]),
+        OpponentState::default(),
+    );
+
+    game.start();
+    game.app.world.resource_mut::<PlayerState>().available_power = 2;
+
+    let first = hand(&mut game.app.world)[0];
+
+    game.play(first, 0);
+
+    let second = hand(&mut game.app.world)[0];
+
+    game.play(second, 1);
+    game.app
+        .world
+        .send_event(PickingEvent::Hover(HoverEvent::JustEntered(second)));
+    game.app.update();
+    game.app.update();
+
+    let world = &mut game.app.world;
+    let text = world
+        .query_filtered::<&Text, With<TooltipText>>()
+        .single(world);
+
+    assert!(text.sections[0]
+        .value
+        .contains("Attack 2 (base 1, +1 from Pitchfork)"));
+    assert_eq!(
+        *world
+            .query_filtered::<&Visibility, With<Tooltip>>()
+            .single(world),
+        Visibility::Inherited
+    );
+
+    world.send_event(PickingEvent::Hover(HoverEvent::JustLeft(second)));
+    game.app.update();
+    game.app.update();
+
+    let world = &mut game.app.world;
+
+    assert_eq!(
+        *world
+            .query_filtered::<&Visibility, With<Tooltip>>()
+            .single(world),
+        Visibility::Hidden
+    );
+}
+
+#[test]
+fn hovering_a_placement_previews_auras() {
+    let mut game = TestGame::new(
+        PlayerState::default().with_cards(vec![CardType::Pitchfork; 6]),
+        OpponentState::default(),
+    );
+
+    game.start();
+    game.app.world.resource_mut::<PlayerState>().available_power = 2;
+
+    let first = hand(&mut game.app.world)[0];
+
+    game.play(first, 0);
+
+    let second = hand(&mut game.app.world)[0];
+    let placeholder = game.placeholder(1);
+
+    game.click(second);
+    game.app
+        .world
+        .send_event(PickingEvent::Hover(HoverEvent::JustEntered(placeholder)));
+    game.app.update();
+    game.app.update();
+
+    let world = &mut game.app.world;
+    let previews = world
+        .query::<&Preview>()
+        .iter(world)
+        .copied()
+        .collect::<Vec<_>>();
+
+    assert_eq!(previews.len(), 2);
+    assert!(previews.contains(&Preview::Buffed));
+    assert!(previews.contains(&Preview::BuffedBy));
+
+    world.send_event(PickingEvent::Hover(HoverEvent::JustLeft(placeholder)));
+    game.app.update();
+    game.app.update();
+
+    let world = &mut game.app.world;
+
+    assert_eq!(world.query::<&Preview>().iter(world).count(), 0);
+}
+
+#[test]
+fn undoing_a_placement_returns_the_card_and_power() {
+    let mut game = TestGame::new(
+        PlayerState::default().with_cards(vec![CardType::Pitchfork; 6]),
+        OpponentState::default(),
+    );
+
+    game.start();
+    game.app.world.resource_mut::<PlayerState>().available_power = 2;
+
+    let first = hand(&mut game.app.world)[0];
+
+    game.play(first, 0);
+
+    let second = hand(&mut game.app.world)[0];
+
+    game.play(second, 1);
+
+    assert_eq!(attack(&game.app.world, first), 2);
+
+    game.press(KeyCode::Back);
+
+    let world = &mut game.app.world;
+
+    assert_eq!(attack(world, first), 1);
+    assert_eq!(world.resource::<PlayerBoard>().all().len(), 1);
+    assert_eq!(world.resource::<PlayerState>().available_power, 1);
+    assert!(hand(world).contains(&second));
+}
+
+#[test]
+fn ctrl_z_and_right_click_undo_through_the_default_bindings() {
+    let mut game = TestGame::new(
+        PlayerState::default().with_cards(vec![CardType::Pitchfork; 6]),
+        OpponentState::default(),
+    );
+
+    game.start();
+    game.app.world.resource_mut::<PlayerState>().available_power = 2;
+
+    let first = hand(&mut game.app.world)[0];
+
+    game.play(first, 0);
+
+    let second = hand(&mut game.app.world)[0];
+
+    game.play(second, 1);
+    game.app
+        .world
+        .resource_mut::<Input<KeyCode>>()
+        .press(KeyCode::LControl);
+    game.press(KeyCode::Z);
+
+    assert_eq!(game.app.world.resource::<PlayerBoard>().all().len(), 1);
+
+    game.app
+        .world
+        .resource_mut::<Input<MouseButton>>()
+        .press(MouseButton::Right);
+    game.app.update();
+    game.app.world.resource_mut::<Input<MouseButton>>().clear();
+    game.app.update();
+
+    let world = &mut game.app.world;
+
+    assert!(world.resource::<PlayerBoard>().all().is_empty());
+    assert_eq!(world.resource::<PlayerState>().available_power, 2);
+    assert!(hand(world).contains(&first));
+}
+
+#[test]
+fn skipping_animations_resolves_turns_without_tweens() {
+    let mut game = TestGame::new(
+        PlayerState::default().with_cards(vec![CardType::Pitchfork; 6]),
+        OpponentState::default(),
+    );
+
+    game.app.world.resource_mut::<Settings>().skip_animations = true;
+    game.start();
+
+    let card = hand(&mut game.app.world)[0];
+
+    game.play(card, 0);
+
+    let dial = game.single::<TurnDial>();
+
+    game.click(dial);
+    game.run_until(|world| state(world) == GameState::OpponentAttacking);
+    game.run_until(|world| state(world) == GameState::PlayerTurn);
+
+    let world = &mut game.app.world;
+    let animators = world.query::<&Animator<Transform>>().iter(world).count();
+    let acting = world.query::<&PerformingAction>().iter(world).count();
+
+    assert_eq!(animators, 0);
+    assert_eq!(acting, 0);
+}
+
+#[test]
+fn phoenix_revives_the_last_fallen_card_into_the_nearest_lane() {
+    let mut game = TestGame::new(
+        PlayerState::default().with_cards(vec![CardType::Phoenix; 6]),
+        OpponentState::default(),
+    );
+
+    game.start();
+
+    let mut player_state = game.app.world.resource_mut::<PlayerState>();
+
+    player_state.available_power = 3;
+    player_state.bury(CardType::Sword);
+
+    let card = hand(&mut game.app.world)[0];
+
+    game.play(card, 1);
+    game.app.update();
+
+    let world = &game.app.world;
+    let board = world.resource::<PlayerBoard>().all();
+
+    assert_eq!(
+        board
+            .iter()
+            .map(|placement| placement.card_type)
+            .collect::<Vec<_>>(),
+        vec![CardType::Sword, CardType::Phoenix]
+    );
+    assert_eq!(board[1].entity, card);
+    assert_eq!(attack(world, board[0].entity), 2);
+    assert_eq!(attack(world, card), 2);
+    assert_eq!(world.resource::<PlayerState>().graveyard().size(), 0);
+}
+
+#[test]
+fn unopposed_cards_attack_the_tower() {
+    let mut game = TestGame::new(
+        PlayerState::default().with_cards(vec![CardType::Pitchfork; 6]),
+        OpponentState::default(),
+    );
+
+    game.start();
+
+    let card = hand(&mut game.app.world)[0];
+    let health = game.app.world.resource::<OpponentState>().get_health();
+
+    game.play(card, 0);
+
+    let dial = game.single::<TurnDial>();
+
+    game.click(dial);
+    game.run_until(|world| state(world) == GameState::PlayerAttacking);
+    game.run_until(|world| state(world) != GameState::PlayerAttacking);
+
+    let world = &game.app.world;
+
+    assert_eq!(state(world), GameState::OpponentPlayCards);
+    assert_eq!(
+        world.resource::<OpponentState>().get_health(),
+        health - attack(world, card)
+    );
+}
+
+#[test]
+fn temporary_buffs_wear_off_on_the_owners_next_turn() {
+    let mut opponent_state = OpponentState::default();
+
+    opponent_state.max_power = 0;
+
+    let mut game = TestGame::new(
+        PlayerState::default().with_cards(vec![CardType::Pitchfork; 6]),
+        opponent_state,
+    );
+
+    game.start();
+
+    let card = hand(&mut game.app.world)[0];
+    let turn = game.app.world.resource::<PlayerState>().turn;
+
+    game.play(card, 0);
+
+    let mut entity = game.app.world.entity_mut(card);
+    let mut modifiers = entity.take::<StatModifiers>().unwrap();
+
+    modifiers.add_until(
+        ModifierSource::Puzzle,
+        CardAbilityEffect {
+            attack: 2,
+            health: 0,
+        },
+        Some(turn + 1),
+    );
+    entity.insert((
+        modifiers.attack(CardType::Pitchfork),
+        modifiers.health(CardType::Pitchfork),
+        modifiers,
+    ));
+
+    assert_eq!(attack(&game.app.world, card), 3);
+
+    let dial = game.single::<TurnDial>();
+
+    game.click(dial);
+    game.run_until(|world| {
+        state(world) == GameState::PlayerTurn && world.resource::<PlayerState>().turn == turn + 1
+    });
+    game.app.update();
+
+    assert_eq!(attack(&game.app.world, card), 1);
+}
+
+#[test]
+fn both_sides_fatigue_on_the_first_turn_they_cannot_draw() {
+    let cards = vec![CardType::Pitchfork; 5];
+    let mut opponent_state = OpponentState::default().with_cards(cards.clone());
+
+    // Without power the opponent never plays, so fatigue is the only damage.
+    opponent_state.max_power = 0;
+
+    let mut game = TestGame::new(PlayerState::default().with_cards(cards), opponent_state);
+
+    game.app.insert_resource(
+        MenuSelection::Small
+            .game_config()
+            .with_deck_out(DeckOutRule::Fatigue),
+    );
+    game.start();
+
+    let player_health = game.app.world.resource::<PlayerState>().get_health();
+    let opponent_health = game.app.world.resource::<OpponentState>().get_health();
+    let mut health = Vec::new();
+
+    for turn in 2..=4 {
+        let dial = game.single::<TurnDial>();
+
+        game.click(dial);
+        game.run_until(|world| {
+            state(world) == GameState::PlayerTurn && world.resource::<PlayerState>().turn == turn
+        });
+
+        let world = &game.app.world;
+
+        health.push((
+            player_health - world.resource::<PlayerState>().get_health(),
+            opponent_health - world.resource::<OpponentState>().get_health(),
+        ));
+    }
+
+    // Each deck runs out on its second turn and fatigue starts on the third.
+    assert_eq!(health, vec![(0, 0), (1, 0), (3, 1)]);
+
+    let stats = game.app.world.resource::<MatchStats>();
+
+    assert_eq!(stats.damage_dealt, 0);
+    assert_eq!(stats.damage_taken, 0);
+}
+
+#[test]
+fn losing_all_health_ends_the_game() {
+    let mut game = TestGame::new(PlayerState::default(), OpponentState::default());
+
+    game.start();
+
+    let damage = game.app.world.resource::<PlayerState>().health;
+
+    game.app
+        .world
+        .resource_mut::<PlayerState>()
+        .take_damage(damage);
+    game.app
+        .world
+        .send_event(AttackedEvent::Player(damage as u32, DamageSource::Attack));
+    game.run_until(|world| state(world) == GameState::Lose);
+    game.app.update();
+
+    assert!(game.app.world.resource::<PlayerState>().sent_to_menu);
+}
+
+#[test]
+fn rematch_restarts_with_the_same_seed() {
+    let mut game = TestGame::new(PlayerState::default(), OpponentState::default());
+
+    game.start();
+
+    let damage = game.app.world.resource::<PlayerState>().health;
+
+    game.app
+        .world
+        .resource_mut::<PlayerState>()
+        .take_damage(damage);
+    game.app
+        .world
+        .send_event(AttackedEvent::Player(damage as u32, DamageSource::Attack));
+    game.run_until(|world| state(world) == GameState::Lose);
+    game.app.update();
+
+    let world = &mut game.app.world;
+    let rematch = world
+        .query::<(Entity, &ResultsButton)>()
+        .iter(world)
+        .find(|(_, button)| matches!(button, ResultsButton::Rematch))
+        .map(|(entity, _)| entity)
+        .unwrap();
+    let seed = world.resource::<GameConfig>().seed;
+
+    world.entity_mut(rematch).insert(Interaction::Clicked);
+    game.run_until(|world| state(world) == GameState::Mulligan);
+
+    let world = &mut game.app.world;
+
+    assert_eq!(world.resource::<GameConfig>().seed, seed);
+    assert!(world.query::<&ResultsScreen>().iter(world).next().is_none());
+}
+
+#[test]
+fn puzzles_are_won_or_lost_in_a_single_turn() {
+    let solutions = [
+        vec![(CardType::Pitchfork, 2), (CardType::Pitchfork, 3)],
+        vec![(CardType::Sword, 2)],
+        vec![(CardType::Sword, 2)],
+    ];
+    let puzzles = Puzzle::bundled();
+
+    assert_eq!(puzzles.len(), solutions.len());
+
+    for (puzzle, solution) in puzzles.into_iter().zip(solutions) {
+        for (solve, outcome) in [(true, GameState::Win), (false, GameState::Lose)] {
+            let mut game = TestGame::new(puzzle.player_state(), puzzle.opponent_state());
+
+            game.app.insert_resource(ActivePuzzle(puzzle.clone()));
+            game.app
+                .world
+                .resource_mut::<NextState<GameState>>()
+                .set(GameState::StartGame);
+            game.run_until(|world| state(world) == GameState::PlayerTurn);
+
+            let world = &mut game.app.world;
+
+            assert_eq!(
+                world.resource::<PlayerBoard>().all().len(),
+                puzzle.player_board.iter().flatten().count()
+            );
+            assert_eq!(hand(world).len(), puzzle.hand.len());
+
+            for (entity, card) in world.query::<(Entity, &CardType)>().iter(world) {
+                if let Some(modifiers) = world.get::<StatModifiers>(entity) {
+                    assert!(
+                        !modifiers.contains(entity),
+                        "{} buffs itself in {}",
+                        card.name(),
+                        puzzle.name
+                    );
+                }
+            }
+
+            if solve {
+                for (card_type, lane) in solution.iter() {
+                    let world = &mut game.app.world;
+                    let card = hand(world)
+                        .into_iter()
+                        .find(|card| world.get::<CardType>(*card) == Some(card_type))
+                        .unwrap();
+
+                    game.play(card, *lane);
+                }
+            }
+
+            let dial = game.single::<TurnDial>();
+
+            game.click(dial);
+            game.run_until(|world| state(world) == outcome);
+        }
+    }
+}
+
+#[test]
+fn tutorial_only_accepts_the_highlighted_action() {
+    let tutorial = Tutorial::load();
+    let mut game = TestGame::new(tutorial.player_state(), tutorial.opponent_state());
+
+    game.app.insert_resource(tutorial);
+    game.app
+        .world
+        .resource_mut::<NextState<GameState>>()
+        .set(GameState::StartGame);
+    game.run_until(|world| state(world) == GameState::Mulligan);
+    game.run_until(|world| world.query::<&Draw>().iter(world).next().is_none());
+
+    let dial = game.single::<TurnDial>();
+
+    game.click(dial);
+    game.app.update();
+
+    assert_eq!(state(&game.app.world), GameState::Mulligan);
+
+    game.press(KeyCode::Return);
+    game.run_until(|world| world.resource::<Tutorial>().target == Some(dial));
+    game.click(dial);
+    game.run_until(|world| state(world) == GameState::PlayerTurn);
+    game.press(KeyCode::Return);
+
+    let cards = hand(&mut game.app.world);
+    let lane = game.placeholder(1);
+
+    game.run_until(|world| world.resource::<Tutorial>().target == Some(cards[0]));
+    game.click(cards[1]);
+
+    assert!(game.app.world.get::<Picked>(cards[1]).is_none());
+
+    game.play(cards[0], 0);
+
+    assert!(game.app.world.resource::<PlayerBoard>().unoccupied(0));
+
+    game.run_until(|world| world.resource::<Tutorial>().target == Some(lane));
+    game.click(lane);
+
+    let world = &mut game.app.world;
+
+    assert_eq!(world.resource::<PlayerBoard>().all()[0].entity, cards[0]);
+    assert_eq!(world.get::<CardType>(cards[0]), Some(&CardType::Pitchfork));
+
+    // Confirming the next step also clicks the focused dial, which must not end the turn.
+    game.press(KeyCode::Return);
+    game.run_until(|world| world.resource::<Tutorial>().target == Some(dial));
+
+    assert_eq!(state(&game.app.world), GameState::PlayerTurn);
+
+    game.click(dial);
+    game.run_until(|world| state(world) == GameState::PlayerTurn);
+
+    let opponent = game.app.world.resource::<OpponentBoard>();
+
+    assert!(!opponent.unoccupied(1));
+    assert_eq!(opponent.all()[0].card_type, CardType::Pitchfork);
+
+    game.press(KeyCode::Return);
+
+    for (card_type, index) in [(CardType::Sword, 1), (CardType::Pitchfork, 2)] {
+        game.run_until(|world| world.resource::<Tutorial>().target.is_some());
+
+        let world = &mut game.app.world;
+        let card = hand(world)[0];
+
+        assert_eq!(world.resource::<Tutorial>().target, Some(card));
+        assert_eq!(world.get::<CardType>(card), Some(&card_type));
+
+        let lane = game.placeholder(index);
+
+        game.click(card);
+        game.run_until(|world| world.resource::<Tutorial>().target == Some(lane));
+        game.click(lane);
+
+        if card_type == CardType::Sword {
+            game.run_until(|world| world.resource::<Tutorial>().target == Some(dial));
+            game.click(dial);
+            game.run_until(|world| state(world) == GameState::PlayerTurn);
+
+            let opponent = game.app.world.resource::<OpponentBoard>();
+
+            assert!(!opponent.unoccupied(3));
+            assert_eq!(opponent.all().len(), 1);
+            assert_eq!(opponent.all()[0].card_type, CardType::Sword);
+
+            game.press(KeyCode::Return);
+        }
+    }
+
+    game.run_until(|world| !world.resource::<PlayerBoard>().unoccupied(2));
+    game.press(KeyCode::Return);
+    game.press(KeyCode::Return);
+
+    let world = &game.app.world;
+
+    assert!(!world.resource::<Tutorial>().locked());
+    assert_eq!(state(world), GameState::PlayerTurn);
+}
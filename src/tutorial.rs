@@ -0,0 +1,170 @@
+use bevy::prelude::*;
+use serde::Deserialize;
+use std::collections::VecDeque;
+
+use crate::{
+    cards::CardType,
+    deck::{OpponentState, PlayableState, PlayerState},
+    menu::{GameConfig, MenuSelection},
+    states::GameState,
+};
+
+pub const TUTORIAL: &str = include_str!("../assets/tutorial.ron");
+
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq)]
+pub enum Highlight {
+    Dial,
+    HandCard(u32),
+    None,
+    Placeholder(u32),
+    Power(u32),
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq)]
+pub struct OpponentMove {
+    pub card_type: CardType,
+    pub lane: u32,
+}
+
+#[derive(Resource)]
+pub struct Tutorial {
+    script: TutorialScript,
+    step: usize,
+    pub target: Option<Entity>,
+}
+
+impl Tutorial {
+    pub fn load() -> Self {
+        Self {
+            script: ron::from_str(TUTORIAL).expect("bundled tutorial is valid"),
+            step: 0,
+            target: None,
+        }
+    }
+
+    pub fn advance(&mut self) {
+        self.step += 1;
+        self.target = None;
+    }
+
+    pub fn allows(&self, entity: Entity) -> bool {
+        self.current().is_none() || self.target == Some(entity)
+    }
+
+    pub fn current(&self) -> Option<&TutorialStep> {
+        self.script.steps.get(self.step)
+    }
+
+    pub fn finish(&mut self) {
+        self.step = self.script.steps.len();
+        self.target = None;
+    }
+
+    pub fn game_config(&self) -> GameConfig {
+        let mut config = MenuSelection::Small.game_config();
+
+        config.deck_size = self.script.player_deck.len() as u32;
+        config.opponent_hp = self.script.opponent_health.max(0) as u32;
+        config
+    }
+
+    pub fn locked(&self) -> bool {
+        self.current().is_some()
+    }
+
+    pub fn opponent_moves(&mut self, turn: u32) -> Option<&mut VecDeque<OpponentMove>> {
+        let index = (turn as usize).checked_sub(1)?;
+
+        self.script.opponent_moves.get_mut(index)
+    }
+
+    pub fn opponent_state(&self) -> OpponentState {
+        OpponentState::default()
+            .with_stacked_deck(self.script.opponent_deck.clone())
+            .with_health(self.script.opponent_health)
+    }
+
+    pub fn player_state(&self) -> PlayerState {
+        PlayerState::default().with_stacked_deck(self.script.player_deck.clone())
+    }
+}
+
+#[derive(Component)]
+pub struct TutorialButton;
+
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq)]
+pub enum TutorialGoal {
+    CardPicked,
+    CardPlaced(u32),
+    Continue,
+    Phase(GameState),
+}
+
+#[derive(Component)]
+pub struct TutorialHighlight;
+
+#[derive(Clone, Copy, Component)]
+pub enum TutorialOption {
+    Next,
+    Skip,
+}
+
+#[derive(Component)]
+pub struct TutorialPanel;
+
+#[derive(Clone, Debug, Deserialize)]
+struct TutorialScript {
+    opponent_deck: Vec<CardType>,
+    opponent_health: i32,
+    opponent_moves: Vec<VecDeque<OpponentMove>>,
+    player_deck: Vec<CardType>,
+    steps: Vec<TutorialStep>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct TutorialStep {
+    pub highlight: Highlight,
+    pub text: String,
+    pub until: TutorialGoal,
+}
+
+#[derive(Component)]
+pub struct TutorialText;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bundled_tutorial_is_valid() {
+        let mut tutorial = Tutorial::load();
+        let steps = tutorial.script.steps.clone();
+
+        assert!(!steps.is_empty());
+
+        for step in steps {
+            match step.highlight {
+                Highlight::HandCard(index) => assert!(index < 5, "{}", step.text),
+                Highlight::Placeholder(index) => assert!(index < 4, "{}", step.text),
+                _ => {}
+            }
+
+            if let TutorialGoal::CardPlaced(index) = step.until {
+                assert_eq!(
+                    step.highlight,
+                    Highlight::Placeholder(index),
+                    "{}",
+                    step.text
+                );
+            }
+        }
+
+        for turn in 1..=tutorial.script.opponent_moves.len() as u32 {
+            for opponent_move in tutorial.opponent_moves(turn).unwrap().iter() {
+                assert!(opponent_move.lane < 4);
+            }
+        }
+
+        assert!(tutorial.opponent_moves(0).is_none());
+    }
+}
@@ -0,0 +1,36 @@
+use bevy::prelude::*;
+
+#[derive(Component, Default)]
+pub struct ForecastPanel {
+    pub hovered: bool,
+}
+
+#[derive(Component)]
+pub struct PhaseBanner {
+    pub timer: Timer,
+}
+
+impl Default for PhaseBanner {
+    fn default() -> Self {
+        Self {
+            timer: Timer::from_seconds(1.5, TimerMode::Once),
+        }
+    }
+}
+
+#[derive(Component)]
+pub struct StatsPanel;
+
+#[derive(Component)]
+pub struct ThinkingIndicator;
+
+#[derive(Component, Default)]
+pub struct Tooltip(pub Option<Entity>);
+
+#[derive(Component)]
+pub struct TooltipText;
+
+#[derive(Resource)]
+pub struct UiAssets {
+    pub font: Handle<Font>,
+}